@@ -1,23 +1,196 @@
 complete -c makewiz -n "__fish_use_subcommand" -s c -l compiler -d 'Set the C/C++ compiler name for this Makefile' -r
 complete -c makewiz -n "__fish_use_subcommand" -s e -l executable -d 'Set the C/C++ executable name for this Makefile' -r
+complete -c makewiz -n "__fish_use_subcommand" -l objc-runtime -d 'Set the runtime linked with Objective-C sources in this Makefile [default: objc]' -r -f -a "{objc	The runtime of the compiler (-lobjc),gnustep	GNUstep\, with the flags and libraries from `gnustep-config`}"
+complete -c makewiz -n "__fish_use_subcommand" -l preset -d 'Set the C/C++ compiler driver preset for this Makefile, replacing the compiler name' -r -f -a "{gcc	GCC (gcc\, g++),clang	Clang (clang\, clang++),zig	Zig as a C/C++ compiler (zig cc\, zig c++),tcc	Tiny C Compiler (tcc)\, C only,icx	Intel oneAPI compilers (icx\, icpx)}"
+complete -c makewiz -n "__fish_use_subcommand" -l target -d 'Set the target triple to compile for, e.g. aarch64-linux-gnu' -r
+complete -c makewiz -n "__fish_use_subcommand" -l sanitize -d 'Enable sanitizers, e.g. address,undefined' -r
+complete -c makewiz -n "__fish_use_subcommand" -l extension -d 'Set the role of a file extension for this Makefile, e.g. inc=header' -r
+complete -c makewiz -n "__fish_use_subcommand" -l output -d 'Set the path of the generated Makefile' -r -F
 complete -c makewiz -n "__fish_use_subcommand" -s m -l math -d 'Add the math library(-lm) to this Makefile'
 complete -c makewiz -n "__fish_use_subcommand" -s t -l thread -d 'Add the thread library(-lpthread) to this Makefile'
 complete -c makewiz -n "__fish_use_subcommand" -s r -l crypto -d 'Add the crypto library(-lcrypto) to this Makefile'
 complete -c makewiz -n "__fish_use_subcommand" -l cunit -d 'Add the CUnit library(-lcunit) to this Makefile'
 complete -c makewiz -n "__fish_use_subcommand" -l cppunit -d 'Add the CPPUnit library(-lcppunit) to this Makefile'
-complete -c makewiz -n "__fish_use_subcommand" -s h -l help -d 'Print help'
+complete -c makewiz -n "__fish_use_subcommand" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_use_subcommand" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_use_subcommand" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_use_subcommand" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_use_subcommand" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
+complete -c makewiz -n "__fish_use_subcommand" -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c makewiz -n "__fish_use_subcommand" -s V -l version -d 'Print version'
 complete -c makewiz -n "__fish_use_subcommand" -f -a "java" -d 'Generate a Java Makefile'
+complete -c makewiz -n "__fish_use_subcommand" -f -a "jni" -d 'Generate a Makefile for Java with native C/C++ code (JNI)'
+complete -c makewiz -n "__fish_use_subcommand" -f -a "mixed" -d 'Generate a single Makefile for a project with both C/C++ and Java code'
+complete -c makewiz -n "__fish_use_subcommand" -f -a "kotlin" -d 'Generate a Kotlin Makefile, compiling the Java sources after the Kotlin ones'
+complete -c makewiz -n "__fish_use_subcommand" -f -a "scala" -d 'Generate a Scala Makefile using scalac'
 complete -c makewiz -n "__fish_use_subcommand" -f -a "set-compiler" -d 'Set the default C/C++ compiler name'
 complete -c makewiz -n "__fish_use_subcommand" -f -a "set-executable" -d 'Set the default C/C++ executable name'
+complete -c makewiz -n "__fish_use_subcommand" -f -a "set-preset" -d 'Set the default C/C++ compiler driver preset'
+complete -c makewiz -n "__fish_use_subcommand" -f -a "set-java" -d 'Set the default Java options'
+complete -c makewiz -n "__fish_use_subcommand" -f -a "set-extension" -d 'Set the role of a file extension, overriding the built-in extensions'
 complete -c makewiz -n "__fish_use_subcommand" -f -a "default" -d 'Show default values'
+complete -c makewiz -n "__fish_use_subcommand" -f -a "regen" -d 'Regenerate the Makefile with the options recorded in its header'
 complete -c makewiz -n "__fish_use_subcommand" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l main-class -d 'Set the fully qualified name of the main class used by the jar and run targets' -r
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l classpath -d 'Add a JAR or directory to the classpath (JARs in lib/ are added automatically)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l junit-jar -d 'Set the JUnit console launcher JAR used by the test target' -r
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l release -d 'Set the Java release to compile for (javac --release)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l lint -d 'Set the lint profile (javac -Xlint:PROFILE), e.g. all or all,-serial' -r
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l encoding -d 'Set the source file encoding (javac -encoding)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l javac-flags -d 'Add additional flags passed to javac' -r
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l resources -d 'Set the directory with resources copied next to the classes (resources/ is used automatically)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l werror -d 'Treat javac warnings as errors (javac -Werror)'
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l doc -d 'Add a doc target generating the documentation with javadoc'
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l sources-jar -d 'Add a sources-jar target packaging the source files'
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from java" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
 complete -c makewiz -n "__fish_seen_subcommand_from java" -s h -l help -d 'Print help'
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l main-class -d 'Set the fully qualified name of the main class used by the jar and run targets' -r
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l classpath -d 'Add a JAR or directory to the classpath (JARs in lib/ are added automatically)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l junit-jar -d 'Set the JUnit console launcher JAR used by the test target' -r
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l release -d 'Set the Java release to compile for (javac --release)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l lint -d 'Set the lint profile (javac -Xlint:PROFILE), e.g. all or all,-serial' -r
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l encoding -d 'Set the source file encoding (javac -encoding)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l javac-flags -d 'Add additional flags passed to javac' -r
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l resources -d 'Set the directory with resources copied next to the classes (resources/ is used automatically)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l library -d 'Set the name of the native library (detected from System.loadLibrary by default)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l werror -d 'Treat javac warnings as errors (javac -Werror)'
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l doc -d 'Add a doc target generating the documentation with javadoc'
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l sources-jar -d 'Add a sources-jar target packaging the source files'
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
+complete -c makewiz -n "__fish_seen_subcommand_from jni" -s h -l help -d 'Print help'
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l main-class -d 'Set the fully qualified name of the main class used by the jar and run targets' -r
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l classpath -d 'Add a JAR or directory to the classpath (JARs in lib/ are added automatically)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l junit-jar -d 'Set the JUnit console launcher JAR used by the test target' -r
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l release -d 'Set the Java release to compile for (javac --release)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l lint -d 'Set the lint profile (javac -Xlint:PROFILE), e.g. all or all,-serial' -r
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l encoding -d 'Set the source file encoding (javac -encoding)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l javac-flags -d 'Add additional flags passed to javac' -r
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l resources -d 'Set the directory with resources copied next to the classes (resources/ is used automatically)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l werror -d 'Treat javac warnings as errors (javac -Werror)'
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l doc -d 'Add a doc target generating the documentation with javadoc'
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l sources-jar -d 'Add a sources-jar target packaging the source files'
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
+complete -c makewiz -n "__fish_seen_subcommand_from mixed" -s h -l help -d 'Print help'
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l main-class -d 'Set the fully qualified name of the main class used by the jar and run targets' -r
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l classpath -d 'Add a JAR or directory to the classpath (JARs in lib/ are added automatically)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l junit-jar -d 'Set the JUnit console launcher JAR used by the test target' -r
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l release -d 'Set the Java release to compile for (javac --release)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l lint -d 'Set the lint profile (javac -Xlint:PROFILE), e.g. all or all,-serial' -r
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l encoding -d 'Set the source file encoding (javac -encoding)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l javac-flags -d 'Add additional flags passed to javac' -r
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l resources -d 'Set the directory with resources copied next to the classes (resources/ is used automatically)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l werror -d 'Treat javac warnings as errors (javac -Werror)'
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l doc -d 'Add a doc target generating the documentation with javadoc'
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l sources-jar -d 'Add a sources-jar target packaging the source files'
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
+complete -c makewiz -n "__fish_seen_subcommand_from kotlin" -s h -l help -d 'Print help'
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l main-class -d 'Set the fully qualified name of the main class used by the jar and run targets' -r
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l classpath -d 'Add a JAR or directory to the classpath (JARs in lib/ are added automatically)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l junit-jar -d 'Set the JUnit console launcher JAR used by the test target' -r
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l release -d 'Set the Java release to compile for (javac --release)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l lint -d 'Set the lint profile (javac -Xlint:PROFILE), e.g. all or all,-serial' -r
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l encoding -d 'Set the source file encoding (javac -encoding)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l javac-flags -d 'Add additional flags passed to javac' -r
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l resources -d 'Set the directory with resources copied next to the classes (resources/ is used automatically)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l werror -d 'Treat javac warnings as errors (javac -Werror)'
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l doc -d 'Add a doc target generating the documentation with javadoc'
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l sources-jar -d 'Add a sources-jar target packaging the source files'
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
+complete -c makewiz -n "__fish_seen_subcommand_from scala" -s h -l help -d 'Print help'
+complete -c makewiz -n "__fish_seen_subcommand_from set-compiler" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from set-compiler" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from set-compiler" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-compiler" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-compiler" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-compiler" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
 complete -c makewiz -n "__fish_seen_subcommand_from set-compiler" -s h -l help -d 'Print help'
+complete -c makewiz -n "__fish_seen_subcommand_from set-executable" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from set-executable" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from set-executable" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-executable" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-executable" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-executable" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
 complete -c makewiz -n "__fish_seen_subcommand_from set-executable" -s h -l help -d 'Print help'
+complete -c makewiz -n "__fish_seen_subcommand_from set-preset" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from set-preset" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from set-preset" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-preset" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-preset" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-preset" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
+complete -c makewiz -n "__fish_seen_subcommand_from set-preset" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l classpath -d 'Set the default classpath entries (pass no entries to clear them)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l junit-jar -d 'Set the default JUnit console launcher JAR' -r
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l release -d 'Set the default Java release (pass an empty value to use the javac default)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l lint -d 'Set the default lint profile (pass an empty value to disable it)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l werror -d 'Set whether javac warnings are treated as errors by default' -r -f -a "{true	,false	}"
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l encoding -d 'Set the default source file encoding (pass an empty value to use the javac default)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l javac-flags -d 'Set the default additional javac flags' -r
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l resources -d 'Set the default resources directory (pass an empty value to only use resources/)' -r
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l doc -d 'Set whether the doc target is added by default' -r -f -a "{true	,false	}"
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l sources-jar -d 'Set whether the sources-jar target is added by default' -r -f -a "{true	,false	}"
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
+complete -c makewiz -n "__fish_seen_subcommand_from set-java" -s h -l help -d 'Print help'
+complete -c makewiz -n "__fish_seen_subcommand_from set-extension" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from set-extension" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from set-extension" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-extension" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-extension" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from set-extension" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
+complete -c makewiz -n "__fish_seen_subcommand_from set-extension" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c makewiz -n "__fish_seen_subcommand_from default" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from default" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from default" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from default" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from default" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from default" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
 complete -c makewiz -n "__fish_seen_subcommand_from default" -s h -l help -d 'Print help'
-complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from help" -f -a "java" -d 'Generate a Java Makefile'
-complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from help" -f -a "set-compiler" -d 'Set the default C/C++ compiler name'
-complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from help" -f -a "set-executable" -d 'Set the default C/C++ executable name'
-complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from help" -f -a "default" -d 'Show default values'
-complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c makewiz -n "__fish_seen_subcommand_from regen" -l output -d 'Set the path of the generated Makefile' -r -F
+complete -c makewiz -n "__fish_seen_subcommand_from regen" -l force -d 'Overwrite an existing Makefile that wasn\'t generated by MakeWiz'
+complete -c makewiz -n "__fish_seen_subcommand_from regen" -l backup -d 'Save a timestamped backup of the existing Makefile before replacing it'
+complete -c makewiz -n "__fish_seen_subcommand_from regen" -l stdout -d 'Print the generated Makefile instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from regen" -l diff -d 'Show the changes to the existing Makefile as a unified diff instead of writing it'
+complete -c makewiz -n "__fish_seen_subcommand_from regen" -l check -d 'Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn\'t)'
+complete -c makewiz -n "__fish_seen_subcommand_from regen" -s h -l help -d 'Print help'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "java" -d 'Generate a Java Makefile'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "jni" -d 'Generate a Makefile for Java with native C/C++ code (JNI)'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "mixed" -d 'Generate a single Makefile for a project with both C/C++ and Java code'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "kotlin" -d 'Generate a Kotlin Makefile, compiling the Java sources after the Kotlin ones'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "scala" -d 'Generate a Scala Makefile using scalac'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "set-compiler" -d 'Set the default C/C++ compiler name'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "set-executable" -d 'Set the default C/C++ executable name'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "set-preset" -d 'Set the default C/C++ compiler driver preset'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "set-java" -d 'Set the default Java options'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "set-extension" -d 'Set the role of a file extension, overriding the built-in extensions'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "default" -d 'Show default values'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "regen" -d 'Regenerate the Makefile with the options recorded in its header'
+complete -c makewiz -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from java; and not __fish_seen_subcommand_from jni; and not __fish_seen_subcommand_from mixed; and not __fish_seen_subcommand_from kotlin; and not __fish_seen_subcommand_from scala; and not __fish_seen_subcommand_from set-compiler; and not __fish_seen_subcommand_from set-executable; and not __fish_seen_subcommand_from set-preset; and not __fish_seen_subcommand_from set-java; and not __fish_seen_subcommand_from set-extension; and not __fish_seen_subcommand_from default; and not __fish_seen_subcommand_from regen; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
@@ -2,16 +2,166 @@
 //!
 //! It extracts appropriate file names and categorizes them as source files or header files. 
 //! It also generates output file names for source files (.o for C/C++ and .class for Java).
+//! Fortran sources are read to find the modules they provide and use, so they can be compiled in order.
+//! Lexer (.l), parser (.y) and Protocol Buffers (.proto) sources generate C/C++ sources, which are compiled instead of them.
 
+use std::collections::BTreeMap;
 use std::fs;
+use clap::ValueEnum;
+use serde::{Serialize, Deserialize};
 use crate::StringVector;
+use crate::compiler_preset::CompilerDriver;
+
+/// Maps file extensions to the role of the file. Extensions are case-sensitive, so `.C` is C++ while `.c` is C.
+const EXTENSIONS: [(&str, FileType); 22] = [
+    ("c", FileType::Source(Language::C)),
+    ("cpp", FileType::Source(Language::Cpp)),
+    ("cc", FileType::Source(Language::Cpp)),
+    ("cxx", FileType::Source(Language::Cpp)),
+    ("C", FileType::Source(Language::Cpp)),
+    ("java", FileType::Source(Language::Java)),
+    ("s", FileType::Source(Language::Assembly)),
+    ("S", FileType::Source(Language::PreprocessedAssembly)),
+    ("f", FileType::Source(Language::Fortran)),
+    ("f90", FileType::Source(Language::Fortran)),
+    ("F90", FileType::Source(Language::Fortran)),
+    ("m", FileType::Source(Language::ObjC)),
+    ("mm", FileType::Source(Language::ObjCpp)),
+    ("l", FileType::Source(Language::Lex)),
+    ("y", FileType::Source(Language::Yacc)),
+    ("proto", FileType::Source(Language::Proto)),
+    ("h", FileType::Header),
+    ("hpp", FileType::Header),
+    ("hh", FileType::Header),
+    ("hxx", FileType::Header),
+    // Template implementations, included by headers
+    ("inl", FileType::Header),
+    ("tpp", FileType::Header),
+];
+
+/// The role of a file extension set in the user config, overriding the built-in extensions.
+#[derive(Serialize, Deserialize, ValueEnum, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtensionRole {
+    /// A C source file.
+    C,
+    /// A C++ source file.
+    Cpp,
+    /// An assembly source file.
+    Asm,
+    /// A Fortran source file.
+    Fortran,
+    /// An Objective-C source file.
+    Objc,
+    /// An Objective-C++ source file.
+    Objcpp,
+    /// A Flex lexer source file.
+    Lex,
+    /// A Bison parser source file.
+    Yacc,
+    /// A Protocol Buffers definition file.
+    Proto,
+    /// A header file.
+    Header,
+    /// A file that is ignored.
+    Ignore,
+}
+
+impl ExtensionRole {
+    fn file_type(&self) -> FileType {
+        match self {
+            ExtensionRole::C => FileType::Source(Language::C),
+            ExtensionRole::Cpp => FileType::Source(Language::Cpp),
+            ExtensionRole::Asm => FileType::Source(Language::Assembly),
+            ExtensionRole::Fortran => FileType::Source(Language::Fortran),
+            ExtensionRole::Objc => FileType::Source(Language::ObjC),
+            ExtensionRole::Objcpp => FileType::Source(Language::ObjCpp),
+            ExtensionRole::Lex => FileType::Source(Language::Lex),
+            ExtensionRole::Yacc => FileType::Source(Language::Yacc),
+            ExtensionRole::Proto => FileType::Source(Language::Proto),
+            ExtensionRole::Header => FileType::Header,
+            ExtensionRole::Ignore => FileType::Other,
+        }
+    }
+}
+
+/// The language of a source file, deciding how it's compiled.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Language {
+    C,
+    Cpp,
+    Java,
+    /// Assembly (.s), compiled by the assembler.
+    Assembly,
+    /// Assembly run through the C preprocessor (.S), compiled by the C/C++ compiler driver.
+    PreprocessedAssembly,
+    /// Fortran (.f, .f90, .F90), compiled by the Fortran compiler.
+    Fortran,
+    /// Objective-C (.m), compiled by the C/C++ compiler driver with `OBJCFLAGS`.
+    ObjC,
+    /// Objective-C++ (.mm), compiled by the C/C++ compiler driver with `OBJCFLAGS`.
+    ObjCpp,
+    /// Flex lexer (.l), generating a `.yy.c` source.
+    Lex,
+    /// Bison parser (.y), generating a `.tab.c` source and a `.tab.h` header.
+    Yacc,
+    /// Protocol Buffers definitions (.proto), generating a `.pb.cc` source and a `.pb.h` header.
+    Proto,
+}
+
+impl Language {
+    /// Returns the extension of the compiled files, e.g. `o` for C/C++.
+    ///
+    /// Lexers, parsers and protobuf definitions are compiled from the generated sources, e.g. `parser.tab.o` from `parser.tab.c`.
+    fn compiled_extension(&self) -> &'static str {
+        match self {
+            Language::Java => "class",
+            Language::Lex => "yy.o",
+            Language::Yacc => "tab.o",
+            Language::Proto => "pb.o",
+            _ => "o",
+        }
+    }
+
+    /// Returns the extensions of the files generated from the sources, e.g. `tab.c` and `tab.h` for Bison.
+    fn generated_extensions(&self) -> &'static [&'static str] {
+        match self {
+            Language::Lex => &["yy.c"],
+            Language::Yacc => &["tab.c", "tab.h"],
+            Language::Proto => &["pb.cc", "pb.h"],
+            _ => &[],
+        }
+    }
+
+    /// Checks if the language is compiled to native object files, linked into the executable.
+    fn is_native(&self) -> bool {
+        !matches!(self, Language::Java)
+    }
+}
+
+/// The Objective-C runtime linked into programs with Objective-C sources.
+#[derive(ValueEnum, PartialEq, Debug, Clone, Copy, Default)]
+pub enum ObjcRuntime {
+    /// The runtime of the compiler (-lobjc).
+    #[default]
+    Objc,
+    /// GNUstep, with the flags and libraries from `gnustep-config`.
+    Gnustep,
+}
 
-const SOURCE_EXTENSIONS: [&str; 3] = ["c", "cpp", "java"]; 
-const HEADER_EXTENSIONS: [&str; 2] = ["h", "hpp"]; 
+/// Modules provided and used by a Fortran source file.
+#[derive(PartialEq, Debug, Default)]
+pub struct FortranModules {
+    /// Names of the modules declared in the file, in lowercase.
+    pub provided: Vec<String>,
+
+    /// Names of the non-intrinsic modules used by the file, in lowercase.
+    pub used: Vec<String>,
+}
 
 /// A struct that holds data for generating a Makefile.
 ///
-/// *Note: Java Makefiles use only the `source_files` and `compiled_files` fields.*
+/// *Note: Java Makefiles are generated from [`JavaData`](crate::java_data::JavaData), which also handles packages.*
 #[derive(PartialEq, Debug)]
 pub struct BuildData {
     /// The name of the C/C++ compiler used for building the project.
@@ -20,13 +170,13 @@ pub struct BuildData {
     /// The name of the executable file that will be generated after compilation.
     pub executable: String,
 
-    /// Collection of header files (.h, .hpp).
+    /// Collection of header files (.h, .hpp, .hh, .hxx) and template implementation files (.inl, .tpp).
     pub header_files: StringVector,
 
     /// Collection of compiled files (.o for C/C++ and .class for Java).
     pub compiled_files: StringVector,
 
-    /// Collection of source files (.c, .cpp, .java).
+    /// Collection of source files (.c, .cpp, .cc, .cxx, .C, .s, .S, .java).
     pub source_files: StringVector,
 
     /// Additional linker flags for the project.
@@ -34,6 +184,18 @@ pub struct BuildData {
 
     /// Additional library flags for linking.
     pub ldlibs: String,
+
+    /// Custom extension mappings from the user config, consulted before the built-in extensions.
+    pub extensions: BTreeMap<String, ExtensionRole>,
+
+    /// Modules of the Fortran source files, by file name.
+    pub fortran_modules: BTreeMap<String, FortranModules>,
+
+    /// The Objective-C runtime, used if there are Objective-C sources.
+    pub objc_runtime: ObjcRuntime,
+
+    /// The compiler driver preset replacing `compiler`, with its target and sanitizers. `None` uses `compiler` as is.
+    pub driver: Option<CompilerDriver>,
 }
 
 impl BuildData {
@@ -47,6 +209,10 @@ impl BuildData {
             source_files: StringVector::new(),
             lflags: String::new(),
             ldlibs: String::new(),
+            extensions: BTreeMap::new(),
+            fortran_modules: BTreeMap::new(),
+            objc_runtime: ObjcRuntime::default(),
+            driver: None,
         }
     }
 
@@ -54,23 +220,9 @@ impl BuildData {
         let mut output_files: Vec<String> = Vec::new();
 
         for source_file in &self.source_files.0 {
-            let mut new_file = source_file.clone();
-            let name_len = source_file.len();
-
-            if get_extension(&source_file) == "c" {
-                new_file.truncate(name_len - 2);
-                new_file.push_str(".o");
-            }
-            else if get_extension(&source_file) == "cpp" {
-                new_file.truncate(name_len - 4);
-                new_file.push_str(".o");
+            if let FileType::Source(language) = FileType::get_extension_type(source_file, &self.extensions) {
+                output_files.push(format!("{}.{}", get_stem(source_file), language.compiled_extension()));
             }
-            else {
-                new_file.truncate(name_len - 5);
-                new_file.push_str(".class");
-            }
-
-            output_files.push(new_file);
         }
 
         self.compiled_files = StringVector(output_files)
@@ -82,26 +234,52 @@ impl BuildData {
     /// # Arguments
     ///
     /// * `paths` - A `ReadDir` iterator representing the directory to scan.
-    /// * `allowed_extensions` - An array of allowed file extensions. Any other extensions will be ignored.
     ///
     /// # Returns
     ///
     /// A `Result` containing the extracted `BuildData` or an error.
     pub fn extract_names(paths: fs::ReadDir) -> Result<BuildData, Box<dyn std::error::Error>> {
+        BuildData::extract_names_with_extensions(paths, BTreeMap::new())
+    }
+
+    /// Extracts file names like [`BuildData::extract_names`], with custom extension mappings
+    /// taking precedence over the built-in extensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - A `ReadDir` iterator representing the directory to scan.
+    /// * `extensions` - Extensions (without the leading dot) mapped to their roles.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the extracted `BuildData` or an error.
+    pub fn extract_names_with_extensions(paths: fs::ReadDir, extensions: BTreeMap<String, ExtensionRole>) -> Result<BuildData, Box<dyn std::error::Error>> {
         let mut files = BuildData::new();
+        files.extensions = extensions;
 
         for path_result in paths {
             let path = path_result?;
             let name = path.path().file_name().unwrap().to_str().unwrap().to_string();
-            let extension = FileType::get_extension_type(&name);
+            let extension = FileType::get_extension_type(&name, &files.extensions);
+
+            if extension == FileType::Source(Language::Fortran) {
+                let code = String::from_utf8_lossy(&fs::read(path.path())?).to_string();
+                let fixed_form = get_extension(&name) == "f";
+                files.fortran_modules.insert(name.clone(), read_fortran_modules(&code, fixed_form));
+            }
 
             match extension {
-                FileType::Source => files.source_files.0.push(name),
+                FileType::Source(_) => files.source_files.0.push(name),
                 FileType::Header => files.header_files.0.push(name),
                 FileType::Other => {}
             }
         }
 
+        // Files generated by a previous build are compiled from their lexer/parser/protobuf sources
+        let generated_files = files.get_generated_files();
+        files.source_files.0.retain(|file| !generated_files.0.contains(file));
+        files.header_files.0.retain(|file| !generated_files.0.contains(file));
+
         files.source_files.0.sort();
         files.header_files.0.sort();
         files.generate_compiled_files();
@@ -134,13 +312,12 @@ impl BuildData {
         java_compiled_files
     }
 
-    /// Get C/C++ source files (.c, .cpp) from the `source_files` collection.
+    /// Get C/C++ and assembly source files (.c, .cpp, .cc, .cxx, .C, .s, .S) from the `source_files` collection.
     pub fn get_cpp_source_files(&self) -> StringVector {
         let mut cpp_source_files = StringVector::new();
 
         for file in self.source_files.0.iter() {
-            let extension = get_extension(file);
-            if extension == "c" || extension == "cpp" {
+            if is_native_source(file, &self.extensions) {
                 cpp_source_files.0.push(file.clone())
             }
         }
@@ -148,6 +325,102 @@ impl BuildData {
         cpp_source_files
     }
 
+    /// Get the language of source files with the given extension, taking the custom extensions into account.
+    pub fn get_extension_language(&self, extension: &str) -> Option<Language> {
+        match FileType::get_extension_type(&format!(".{}", extension), &self.extensions) {
+            FileType::Source(language) => Some(language),
+            _ => None,
+        }
+    }
+
+    /// Checks if there are sources in the given language.
+    pub fn has_sources(&self, language: Language) -> bool {
+        self.get_cpp_source_extensions().iter()
+            .any(|extension| self.get_extension_language(extension) == Some(language))
+    }
+
+    /// Get the files generated from the lexer (.l), parser (.y) and protobuf (.proto) sources, sorted.
+    pub fn get_generated_files(&self) -> StringVector {
+        let mut generated_files = Vec::new();
+
+        for source_file in &self.source_files.0 {
+            if let FileType::Source(language) = FileType::get_extension_type(source_file, &self.extensions) {
+                for extension in language.generated_extensions() {
+                    generated_files.push(format!("{}.{}", get_stem(source_file), extension));
+                }
+            }
+        }
+
+        generated_files.sort();
+        StringVector(generated_files)
+    }
+
+    /// Get the headers generated from the parser and protobuf sources (.tab.h, .pb.h), sorted.
+    pub fn get_generated_headers(&self) -> StringVector {
+        StringVector(self.get_generated_files().0.into_iter().filter(|file| get_extension(file) == "h").collect())
+    }
+
+    /// Get the extensions of the generated sources (e.g. `c` for `.tab.c`), sorted and without duplicates.
+    pub fn get_generated_source_extensions(&self) -> Vec<&'static str> {
+        let mut extensions: Vec<&'static str> = self.source_files.0.iter()
+            .filter_map(|file| match FileType::get_extension_type(file, &self.extensions) {
+                FileType::Source(language) => Some(language.generated_extensions()),
+                _ => None,
+            })
+            .flatten()
+            .map(|extension| get_extension(extension))
+            .filter(|extension| *extension != "h")
+            .collect();
+
+        extensions.sort();
+        extensions.dedup();
+        extensions
+    }
+
+    /// Get the module files (.mod) written by the Fortran compiler, sorted.
+    pub fn get_fortran_module_files(&self) -> StringVector {
+        let mut module_files: Vec<String> = self.fortran_modules.values()
+            .flat_map(|modules| modules.provided.iter().map(|name| format!("{}.mod", name)))
+            .collect();
+
+        module_files.sort();
+        module_files.dedup();
+        StringVector(module_files)
+    }
+
+    /// Get the dependencies between Fortran objects: every object is paired with the objects
+    /// of the files providing the modules it uses, which have to be compiled first.
+    pub fn get_fortran_dependencies(&self) -> Vec<(String, StringVector)> {
+        let mut dependencies = Vec::new();
+
+        for (file, modules) in &self.fortran_modules {
+            let mut objects: Vec<String> = self.fortran_modules.iter()
+                .filter(|(other_file, other_modules)| *other_file != file
+                    && other_modules.provided.iter().any(|name| modules.used.contains(name)))
+                .map(|(other_file, _)| format!("{}.o", get_stem(other_file)))
+                .collect();
+
+            if !objects.is_empty() {
+                objects.sort();
+                dependencies.push((format!("{}.o", get_stem(file)), StringVector(objects)));
+            }
+        }
+
+        dependencies
+    }
+
+    /// Get the extensions of the C/C++ source files, sorted and without duplicates.
+    pub fn get_cpp_source_extensions(&self) -> Vec<&str> {
+        let mut extensions: Vec<&str> = self.source_files.0.iter()
+            .filter(|file| is_native_source(file, &self.extensions))
+            .map(|file| get_extension(file))
+            .collect();
+
+        extensions.sort();
+        extensions.dedup();
+        extensions
+    }
+
     /// Get C/C++ compiled files (.o) from the `compiled_files` collection.
     pub fn get_cpp_compiled_files(&self) -> StringVector {
         let mut cpp_compiled_files = StringVector::new();
@@ -164,28 +437,82 @@ impl BuildData {
 
 fn get_extension(file_name: &str) -> &str {
     match file_name.rfind('.') {
-        Some(index) => {
-            let extension = &file_name[index + 1..];
+        Some(index) => &file_name[index + 1..],
+        None => "",
+    }
+}
+
+/// Reads the modules declared and used in Fortran source code.
+///
+/// In fixed-form sources (.f), lines starting with `c`, `C` or `*` are comments.
+fn read_fortran_modules(code: &str, fixed_form: bool) -> FortranModules {
+    let mut modules = FortranModules::default();
 
-            extension
+    for line in code.lines() {
+        if fixed_form && line.starts_with(['c', 'C', '*']) {
+            continue;
         }
-        None => "",
+
+        let line = line.split('!').next().unwrap_or("").trim().to_lowercase();
+
+        // `module procedure` and `module subroutine` are declarations inside modules
+        if let Some(name) = line.strip_prefix("module ") {
+            let name = name.trim();
+            if !name.is_empty() && !name.contains(char::is_whitespace) && name != "procedure" {
+                modules.provided.push(name.to_string());
+            }
+        }
+        else if let Some(rest) = line.strip_prefix("use").filter(|rest| rest.starts_with([' ', ',', ':'])) {
+            let (attributes, rest) = match rest.split_once("::") {
+                Some((attributes, rest)) => (attributes, rest),
+                None => ("", rest),
+            };
+
+            let is_intrinsic = attributes.split(',').any(|attribute| attribute.trim() == "intrinsic");
+            let name = rest.split(',').next().unwrap_or("").trim();
+
+            if !is_intrinsic && !name.is_empty() && !modules.used.iter().any(|used| used == name) {
+                modules.used.push(name.to_string());
+            }
+        }
+    }
+
+    modules
+}
+
+/// Returns the file name without its extension.
+fn get_stem(file_name: &str) -> &str {
+    match file_name.rfind('.') {
+        Some(index) => &file_name[..index],
+        None => file_name,
     }
 }
 
+fn is_native_source(file_name: &str, extensions: &BTreeMap<String, ExtensionRole>) -> bool {
+    matches!(FileType::get_extension_type(file_name, extensions), FileType::Source(language) if language.is_native())
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
 enum FileType {
-    Source,
+    Source(Language),
     Header,
     Other
 }
 
 impl FileType {
-    fn get_extension_type(file_name: &str) -> FileType {
+    fn get_extension_type(file_name: &str, custom_extensions: &BTreeMap<String, ExtensionRole>) -> FileType {
         let extension = get_extension(file_name);
 
-        if SOURCE_EXTENSIONS.contains(&extension) { FileType::Source }
-        else if HEADER_EXTENSIONS.contains(&extension) { FileType::Header }
-        else { FileType::Other }
+        // Extensions in the config may be written with the leading dot
+        let custom_role = custom_extensions.iter()
+            .find(|(custom_extension, _)| custom_extension.trim_start_matches('.') == extension);
+        if let Some((_, role)) = custom_role {
+            return role.file_type();
+        }
+
+        EXTENSIONS.iter()
+            .find(|(known_extension, _)| *known_extension == extension)
+            .map_or(FileType::Other, |(_, file_type)| *file_type)
     }
 }
 
@@ -213,6 +540,10 @@ mod test {
             compiled_files: StringVector(vec![String::from("c_source.o"), String::from("cpp_source.o")]),
             lflags: String::new(),
             ldlibs: String::new(),
+            extensions: BTreeMap::new(),
+            fortran_modules: BTreeMap::new(),
+            objc_runtime: ObjcRuntime::default(),
+            driver: None,
         };
         let result = BuildData::extract_names(paths).unwrap();
 
@@ -230,6 +561,10 @@ mod test {
             source_files: StringVector(vec![String::from("c_source.c"), String::from("cpp_source.cpp")]),
             lflags: String::new(),
             ldlibs: String::new(),
+            extensions: BTreeMap::new(),
+            fortran_modules: BTreeMap::new(),
+            objc_runtime: ObjcRuntime::default(),
+            driver: None,
         };
         let result = BuildData::extract_names(paths).unwrap();
 
@@ -246,6 +581,10 @@ mod test {
             source_files: StringVector(vec![String::from("c_file.c"), String::from("cpp_file.cpp"), String::from("java_file.java")]),
             lflags: String::new(),
             ldlibs: String::new(),
+            extensions: BTreeMap::new(),
+            fortran_modules: BTreeMap::new(),
+            objc_runtime: ObjcRuntime::default(),
+            driver: None,
         };
 
         let expected_source = StringVector(vec![String::from("c_file.c"), String::from("cpp_file.cpp")]);
@@ -256,6 +595,139 @@ mod test {
 
         assert_eq!(expected_source, source_result);
         assert_eq!(expected_compiled, compiled_result);
+        assert_eq!(vec!["c", "cpp"], data.get_cpp_source_extensions());
+    }
+
+    #[test]
+    fn extract_names_extended_extensions() {
+        let paths = fs::read_dir("./test-dirs/test-extracting-filenames/extended-extensions").unwrap();
+        let expected = BuildData {
+            compiler: String::new(),
+            executable: String::new(),
+            header_files: StringVector(vec![String::from("matrix.hh"), String::from("matrix.tpp"),
+                String::from("vector.hxx"), String::from("vector.inl")]),
+            compiled_files: StringVector(vec![String::from("Legacy.o"), String::from("main.o"),
+                String::from("matrix.o"), String::from("vector.o")]),
+            source_files: StringVector(vec![String::from("Legacy.C"), String::from("main.c"),
+                String::from("matrix.cc"), String::from("vector.cxx")]),
+            lflags: String::new(),
+            ldlibs: String::new(),
+            extensions: BTreeMap::new(),
+            fortran_modules: BTreeMap::new(),
+            objc_runtime: ObjcRuntime::default(),
+            driver: None,
+        };
+        let result = BuildData::extract_names(paths).unwrap();
+
+        assert_eq!(expected, result);
+        assert_eq!(vec!["C", "c", "cc", "cxx"], result.get_cpp_source_extensions());
+    }
+
+    #[test]
+    fn getting_extension_types() {
+        let builtin = BTreeMap::new();
+
+        assert_eq!(FileType::Source(Language::Cpp), FileType::get_extension_type("file.C", &builtin));
+        assert_eq!(FileType::Source(Language::C), FileType::get_extension_type("file.c", &builtin));
+        assert_eq!(FileType::Source(Language::Java), FileType::get_extension_type("File.java", &builtin));
+        assert_eq!(FileType::Header, FileType::get_extension_type("file.tpp", &builtin));
+        assert_eq!(FileType::Source(Language::Assembly), FileType::get_extension_type("start.s", &builtin));
+        assert_eq!(FileType::Source(Language::PreprocessedAssembly), FileType::get_extension_type("start.S", &builtin));
+        assert_eq!(FileType::Source(Language::Fortran), FileType::get_extension_type("solver.F90", &builtin));
+        assert_eq!(FileType::Source(Language::ObjC), FileType::get_extension_type("Greeter.m", &builtin));
+        assert_eq!(FileType::Source(Language::ObjCpp), FileType::get_extension_type("Bridge.mm", &builtin));
+        assert_eq!(FileType::Other, FileType::get_extension_type("Makefile", &builtin));
+        assert_eq!(FileType::Other, FileType::get_extension_type("notes.txt", &builtin));
+
+        let custom = BTreeMap::from([
+            (String::from("c++"), ExtensionRole::Cpp),
+            (String::from(".inc"), ExtensionRole::Header),
+            (String::from("tpp"), ExtensionRole::Ignore),
+        ]);
+
+        assert_eq!(FileType::Source(Language::Cpp), FileType::get_extension_type("file.c++", &custom));
+        assert_eq!(FileType::Header, FileType::get_extension_type("file.inc", &custom));
+        assert_eq!(FileType::Other, FileType::get_extension_type("file.tpp", &custom));
+        assert_eq!(FileType::Source(Language::C), FileType::get_extension_type("file.c", &custom));
+    }
+
+    #[test]
+    fn extract_names_custom_extensions() {
+        let paths = fs::read_dir("./test-dirs/test-extracting-filenames/custom-extensions").unwrap();
+        let extensions = BTreeMap::from([
+            (String::from("c++"), ExtensionRole::Cpp),
+            (String::from("ipp"), ExtensionRole::Header),
+            (String::from("hpp"), ExtensionRole::Ignore),
+        ]);
+
+        let result = BuildData::extract_names_with_extensions(paths, extensions).unwrap();
+
+        assert_eq!(StringVector(vec![String::from("main.cpp"), String::from("vendor.c++")]), result.source_files);
+        assert_eq!(StringVector(vec![String::from("main.o"), String::from("vendor.o")]), result.compiled_files);
+        assert_eq!(StringVector(vec![String::from("vendor.ipp")]), result.header_files);
+        assert_eq!(vec!["c++", "cpp"], result.get_cpp_source_extensions());
+    }
+
+    #[test]
+    fn extract_names_lexer_and_parser() {
+        let paths = fs::read_dir("./test-dirs/test-flex-bison").unwrap();
+        let result = BuildData::extract_names(paths).unwrap();
+
+        // parser.tab.c is left over from a previous build
+        assert_eq!(StringVector(vec![String::from("calc.c"), String::from("lexer.l"), String::from("parser.y")]),
+            result.source_files);
+        assert_eq!(StringVector(vec![String::from("calc.h")]), result.header_files);
+        assert_eq!(StringVector(vec![String::from("calc.o"), String::from("lexer.yy.o"), String::from("parser.tab.o")]),
+            result.compiled_files);
+        assert_eq!(StringVector(vec![String::from("lexer.yy.c"), String::from("parser.tab.c"), String::from("parser.tab.h")]),
+            result.get_generated_files());
+        assert_eq!(StringVector(vec![String::from("parser.tab.h")]), result.get_generated_headers());
+        assert_eq!(vec!["c"], result.get_generated_source_extensions());
+    }
+
+    #[test]
+    fn extract_names_fortran_modules() {
+        let paths = fs::read_dir("./test-dirs/test-fortran").unwrap();
+        let result = BuildData::extract_names(paths).unwrap();
+
+        assert_eq!(StringVector(vec![String::from("constants.f90"), String::from("geometry.f90"),
+            String::from("legacy.f"), String::from("main.F90"), String::from("report.c")]), result.source_files);
+        assert_eq!(StringVector(vec![String::from("constants.o"), String::from("geometry.o"),
+            String::from("legacy.o"), String::from("main.o"), String::from("report.o")]), result.compiled_files);
+        assert_eq!(StringVector(vec![String::from("constants.mod"), String::from("geometry.mod")]),
+            result.get_fortran_module_files());
+        assert_eq!(vec![
+            (String::from("geometry.o"), StringVector(vec![String::from("constants.o")])),
+            (String::from("main.o"), StringVector(vec![String::from("geometry.o")])),
+        ], result.get_fortran_dependencies());
+        assert!(result.has_sources(Language::Fortran));
+        assert!(!result.has_sources(Language::Cpp));
+    }
+
+    #[test]
+    fn reading_fortran_modules() {
+        let code = "\
+MODULE Geometry  ! shapes
+  use constants, only: pi
+  use, intrinsic :: iso_c_binding
+  use :: constants
+  interface area
+    module procedure circle_area
+  end interface
+end module Geometry
+";
+        let expected = FortranModules {
+            provided: vec![String::from("geometry")],
+            used: vec![String::from("constants")],
+        };
+        assert_eq!(expected, read_fortran_modules(code, false));
+
+        let fixed_form = "C     module commented\n      program legacy\n      use geometry\n      end\n";
+        let expected = FortranModules {
+            provided: Vec::new(),
+            used: vec![String::from("geometry")],
+        };
+        assert_eq!(expected, read_fortran_modules(fixed_form, true));
     }
 
     #[test]
@@ -268,6 +740,10 @@ mod test {
             source_files: StringVector(vec![String::from("c_file.c"), String::from("cpp_file.cpp"), String::from("java_file.java")]),
             lflags: String::new(),
             ldlibs: String::new(),
+            extensions: BTreeMap::new(),
+            fortran_modules: BTreeMap::new(),
+            objc_runtime: ObjcRuntime::default(),
+            driver: None,
         };
 
         let expected_source = StringVector(vec![String::from("java_file.java")]);
@@ -4,7 +4,13 @@
 //! This module specifies all the available commands and arguments that users can use when
 //! running MakeWiz via the command line. It also handles various C/C++ flags provided by the user.
 
-use clap::{Parser, Subcommand, Args};
+use clap::{Parser, Subcommand, Args, CommandFactory, ValueEnum};
+
+use crate::build_data::{ExtensionRole, ObjcRuntime};
+use crate::compiler_preset::CompilerPreset;
+use crate::user_config::Attribute;
+
+use std::path::PathBuf;
 
 /// This struct defines and handles all the available
 /// commands, options, and flags. 
@@ -22,6 +28,26 @@ pub struct CLI {
     #[arg(short, long, value_name = "EXECUTABLE_NAME")]
     pub executable: Option<String>,
 
+    /// Set the runtime linked with Objective-C sources in this Makefile [default: objc]
+    #[arg(long, value_enum, value_name = "RUNTIME")]
+    pub objc_runtime: Option<ObjcRuntime>,
+
+    /// Set the C/C++ compiler driver preset for this Makefile, replacing the compiler name
+    #[arg(long, value_enum, value_name = "PRESET")]
+    pub preset: Option<CompilerPreset>,
+
+    /// Set the target triple to compile for, e.g. aarch64-linux-gnu
+    #[arg(long, value_name = "TRIPLE")]
+    pub target: Option<String>,
+
+    /// Enable sanitizers, e.g. address,undefined
+    #[arg(long, value_name = "SANITIZERS", value_delimiter = ',')]
+    pub sanitize: Vec<String>,
+
+    /// Set the role of a file extension for this Makefile, e.g. inc=header
+    #[arg(long = "extension", value_name = "EXTENSION=ROLE", value_parser = parse_extension_mapping)]
+    pub extensions: Vec<(String, ExtensionRole)>,
+
     /// Add the math library(-lm) to this Makefile
     #[arg(short, long)]
     math: bool,
@@ -41,13 +67,90 @@ pub struct CLI {
     /// Add the CPPUnit library(-lcppunit) to this Makefile
     #[arg(long)]
     cppunit: bool,
+
+    #[command(flatten)]
+    pub output_args: OutputArgs,
+}
+
+/// Parses an `EXTENSION=ROLE` mapping, e.g. `inc=header`.
+fn parse_extension_mapping(mapping: &str) -> Result<(String, ExtensionRole), String> {
+    let (extension, role) = mapping.split_once('=')
+        .ok_or_else(|| String::from("expected EXTENSION=ROLE"))?;
+    let role = ExtensionRole::from_str(role, true)?;
+
+    Ok((extension.trim_start_matches('.').to_string(), role))
+}
+
+/// Represents the arguments deciding where and how the generated Makefile is written.
+///
+/// These arguments aren't recorded in the provenance header of the Makefile,
+/// so they can be freely combined with `makewiz regen`.
+#[derive(Args)]
+pub struct OutputArgs {
+    /// Overwrite an existing Makefile that wasn't generated by MakeWiz
+    #[arg(long, global = true)]
+    pub force: bool,
+
+    /// Save a timestamped backup of the existing Makefile before replacing it
+    #[arg(long, global = true)]
+    pub backup: bool,
+
+    /// Set the path of the generated Makefile
+    #[arg(long, global = true, value_name = "PATH", default_value = "Makefile")]
+    pub output: PathBuf,
+
+    /// Print the generated Makefile instead of writing it
+    #[arg(long, global = true, conflicts_with = "diff")]
+    pub stdout: bool,
+
+    /// Show the changes to the existing Makefile as a unified diff instead of writing it
+    #[arg(long, global = true)]
+    pub diff: bool,
+
+    /// Check if the existing Makefile is up to date without writing it (exits with code 2 if it isn't)
+    #[arg(long, global = true, conflicts_with_all = ["stdout", "diff"])]
+    pub check: bool,
+}
+
+impl OutputArgs {
+    /// Removes the output arguments from the given command line arguments,
+    /// leaving only the ones that affect the content of the Makefile.
+    pub fn strip_from(args: &[String]) -> Vec<String> {
+        const OUTPUT_FLAGS: [&str; 5] = ["--force", "--backup", "--stdout", "--diff", "--check"];
+
+        let mut stripped = Vec::new();
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            if arg == "--output" {
+                iter.next();
+            }
+            else if !OUTPUT_FLAGS.contains(&arg.as_str()) && !arg.starts_with("--output=") {
+                stripped.push(arg.clone());
+            }
+        }
+
+        stripped
+    }
 }
 
 /// Represents the available commands for the MakeWiz CLI.
 #[derive(Subcommand)]
 pub enum Commands {
     /// Generate a Java Makefile
-    Java,
+    Java(JavaArgs),
+
+    /// Generate a Makefile for Java with native C/C++ code (JNI)
+    Jni(JniArgs),
+
+    /// Generate a single Makefile for a project with both C/C++ and Java code
+    Mixed(JavaArgs),
+
+    /// Generate a Kotlin Makefile, compiling the Java sources after the Kotlin ones
+    Kotlin(JavaArgs),
+
+    /// Generate a Scala Makefile using scalac
+    Scala(JavaArgs),
 
     /// Set the default C/C++ compiler name
     SetCompiler(NameArgument),
@@ -55,8 +158,167 @@ pub enum Commands {
     /// Set the default C/C++ executable name
     SetExecutable(NameArgument),
 
+    /// Set the default C/C++ compiler driver preset
+    SetPreset(PresetArgument),
+
+    /// Set the default Java options
+    SetJava(JavaConfigArgs),
+
+    /// Set the role of a file extension, overriding the built-in extensions
+    SetExtension(ExtensionArgs),
+
     /// Show default values
     Default,
+
+    /// Regenerate the Makefile with the options recorded in its header
+    Regen,
+}
+
+/// Represents the arguments of the `java` subcommand.
+#[derive(Args)]
+pub struct JavaArgs {
+    /// Set the fully qualified name of the main class used by the jar and run targets
+    #[arg(long, value_name = "CLASS_NAME")]
+    pub main_class: Option<String>,
+
+    /// Add a JAR or directory to the classpath (JARs in lib/ are added automatically)
+    #[arg(long = "classpath", value_name = "PATH")]
+    pub classpath: Vec<String>,
+
+    /// Set the JUnit console launcher JAR used by the test target
+    #[arg(long, value_name = "PATH")]
+    pub junit_jar: Option<String>,
+
+    /// Set the Java release to compile for (javac --release)
+    #[arg(long, value_name = "VERSION")]
+    pub release: Option<String>,
+
+    /// Set the lint profile (javac -Xlint:PROFILE), e.g. all or all,-serial
+    #[arg(long, value_name = "PROFILE")]
+    pub lint: Option<String>,
+
+    /// Treat javac warnings as errors (javac -Werror)
+    #[arg(long)]
+    pub werror: bool,
+
+    /// Set the source file encoding (javac -encoding)
+    #[arg(long, value_name = "ENCODING")]
+    pub encoding: Option<String>,
+
+    /// Add additional flags passed to javac
+    #[arg(long, value_name = "FLAGS", allow_hyphen_values = true)]
+    pub javac_flags: Option<String>,
+
+    /// Set the directory with resources copied next to the classes (resources/ is used automatically)
+    #[arg(long, value_name = "DIR")]
+    pub resources: Option<String>,
+
+    /// Add a doc target generating the documentation with javadoc
+    #[arg(long)]
+    pub doc: bool,
+
+    /// Add a sources-jar target packaging the source files
+    #[arg(long)]
+    pub sources_jar: bool,
+}
+
+/// Represents the arguments of the `jni` subcommand.
+///
+/// The C/C++ options (e.g. `-c` and `-m`) apply to the native library.
+#[derive(Args)]
+pub struct JniArgs {
+    #[command(flatten)]
+    pub java_args: JavaArgs,
+
+    /// Set the name of the native library (detected from System.loadLibrary by default)
+    #[arg(long, value_name = "NAME")]
+    pub library: Option<String>,
+}
+
+/// Represents the arguments of the `set-java` subcommand.
+///
+/// Only the provided options are updated in the config file.
+#[derive(Args)]
+#[group(required = true, multiple = true)]
+pub struct JavaConfigArgs {
+    /// Set the default classpath entries (pass no entries to clear them)
+    #[arg(long, value_name = "PATH", num_args = 0..)]
+    pub classpath: Option<Vec<String>>,
+
+    /// Set the default JUnit console launcher JAR
+    #[arg(long, value_name = "PATH")]
+    pub junit_jar: Option<String>,
+
+    /// Set the default Java release (pass an empty value to use the javac default)
+    #[arg(long, value_name = "VERSION")]
+    pub release: Option<String>,
+
+    /// Set the default lint profile (pass an empty value to disable it)
+    #[arg(long, value_name = "PROFILE")]
+    pub lint: Option<String>,
+
+    /// Set whether javac warnings are treated as errors by default
+    #[arg(long, value_name = "BOOL")]
+    pub werror: Option<bool>,
+
+    /// Set the default source file encoding (pass an empty value to use the javac default)
+    #[arg(long, value_name = "ENCODING")]
+    pub encoding: Option<String>,
+
+    /// Set the default additional javac flags
+    #[arg(long, value_name = "FLAGS", allow_hyphen_values = true)]
+    pub javac_flags: Option<String>,
+
+    /// Set the default resources directory (pass an empty value to only use resources/)
+    #[arg(long, value_name = "DIR")]
+    pub resources: Option<String>,
+
+    /// Set whether the doc target is added by default
+    #[arg(long, value_name = "BOOL")]
+    pub doc: Option<bool>,
+
+    /// Set whether the sources-jar target is added by default
+    #[arg(long, value_name = "BOOL")]
+    pub sources_jar: Option<bool>,
+}
+
+impl JavaConfigArgs {
+    /// Converts the provided options to the config attributes they update.
+    pub fn get_attributes(&self) -> Vec<Attribute> {
+        let mut attributes = Vec::new();
+
+        if let Some(classpath) = &self.classpath { attributes.push(Attribute::JavaClasspath(classpath.clone())); }
+        if let Some(junit_jar) = &self.junit_jar { attributes.push(Attribute::JunitJar(junit_jar.clone())); }
+        if let Some(release) = &self.release { attributes.push(Attribute::JavaRelease(release.clone())); }
+        if let Some(lint) = &self.lint { attributes.push(Attribute::JavaLint(lint.clone())); }
+        if let Some(werror) = self.werror { attributes.push(Attribute::JavaWerror(werror)); }
+        if let Some(encoding) = &self.encoding { attributes.push(Attribute::JavaEncoding(encoding.clone())); }
+        if let Some(flags) = &self.javac_flags { attributes.push(Attribute::JavacFlags(flags.clone())); }
+        if let Some(resources) = &self.resources { attributes.push(Attribute::JavaResources(resources.clone())); }
+        if let Some(doc) = self.doc { attributes.push(Attribute::JavaDoc(doc)); }
+        if let Some(sources_jar) = self.sources_jar { attributes.push(Attribute::JavaSourcesJar(sources_jar)); }
+
+        attributes
+    }
+}
+
+/// Represents the arguments of the `set-extension` subcommand.
+#[derive(Args)]
+pub struct ExtensionArgs {
+    /// The file extension, e.g. c++ or inc
+    pub extension: String,
+
+    /// The role of files with this extension (omit it to remove the mapping)
+    #[arg(value_enum)]
+    pub role: Option<ExtensionRole>,
+}
+
+/// Represents the argument of the `set-preset` subcommand.
+#[derive(Args)]
+pub struct PresetArgument {
+    /// The compiler driver preset (omit it to use the compiler name)
+    #[arg(value_enum)]
+    pub preset: Option<CompilerPreset>,
 }
 
 /// Represents a single argument for setting a compiler/executable name.
@@ -72,9 +334,42 @@ impl CLI {
         self.command.is_some()
     }
 
+    /// Checks if a subcommand building C/C++ code, which accepts the C/C++ flags, has been provided by the user.
+    pub fn native_subcommand_provided(&self) -> bool {
+        matches!(self.command, Some(Commands::Jni(_)) | Some(Commands::Mixed(_)))
+    }
+
     /// Checks if flags have been provided by the user.
     pub fn flags_provided(&self) -> bool {
-        self.executable.is_some() || self.compiler.is_some()
+        self.executable.is_some() || self.compiler.is_some() || self.objc_runtime.is_some()
+            || self.preset.is_some() || self.target.is_some() || !self.sanitize.is_empty()
+    }
+
+    /// Finds the position of the subcommand name in the given arguments, skipping the values of the options before it.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The command line arguments, without the program name.
+    ///
+    /// # Returns
+    ///
+    /// The index of the subcommand name, or `None` if there's no subcommand.
+    pub fn find_subcommand(args: &[String]) -> Option<usize> {
+        let command = CLI::command();
+        let takes_value = |arg: &str| command.get_arguments()
+            .filter(|option| option.get_action().takes_values())
+            .any(|option| option.get_long().map(|long| format!("--{}", long)).as_deref() == Some(arg)
+                || option.get_short().map(|short| format!("-{}", short)).as_deref() == Some(arg));
+
+        let mut index = 0;
+        while index < args.len() {
+            if command.find_subcommand(&args[index]).is_some() {
+                return Some(index);
+            }
+            index += if takes_value(&args[index]) { 2 } else { 1 };
+        }
+
+        None
     }
 
     /// Parses the provided flags and returns a tuple containing lflags and ldlibs.
@@ -0,0 +1,281 @@
+//! Knows the command line syntax of the supported C/C++ compiler drivers.
+//!
+//! A preset picks the commands compiling C and C++ sources, and the flags for generating dependency files,
+//! building position independent code, enabling sanitizers and compiling for a target triple.
+//! For example, `zig cc` takes the target with `-target`, while gcc is installed as a separate
+//! driver for every target, e.g. `aarch64-linux-gnu-gcc`.
+
+use clap::ValueEnum;
+use serde::{Serialize, Deserialize};
+
+/// A C/C++ compiler driver with a known command line syntax.
+#[derive(Serialize, Deserialize, ValueEnum, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum CompilerPreset {
+    /// GCC (gcc, g++)
+    Gcc,
+    /// Clang (clang, clang++)
+    Clang,
+    /// Zig as a C/C++ compiler (zig cc, zig c++)
+    Zig,
+    /// Tiny C Compiler (tcc), C only
+    Tcc,
+    /// Intel oneAPI compilers (icx, icpx)
+    Icx,
+}
+
+impl CompilerPreset {
+    /// Detects the preset of a compiler command, e.g. `Gcc` for `g++` or `aarch64-linux-gnu-gcc`.
+    ///
+    /// # Arguments
+    ///
+    /// * `compiler` - The compiler command.
+    ///
+    /// # Returns
+    ///
+    /// The preset, or `None` if the compiler isn't known.
+    pub fn detect(compiler: &str) -> Option<CompilerPreset> {
+        let compiler = compiler.trim();
+        let name = compiler.rsplit('/').next().unwrap_or(compiler);
+
+        match name {
+            _ if name.starts_with("zig ") => Some(CompilerPreset::Zig),
+            _ if name.starts_with("clang") => Some(CompilerPreset::Clang),
+            "icx" | "icpx" => Some(CompilerPreset::Icx),
+            "tcc" => Some(CompilerPreset::Tcc),
+            _ if name == "gcc" || name == "g++" || name.ends_with("-gcc") || name.ends_with("-g++") => Some(CompilerPreset::Gcc),
+            _ => None,
+        }
+    }
+
+    /// Returns the command compiling C sources.
+    fn c_command(&self) -> &'static str {
+        match self {
+            CompilerPreset::Gcc => "gcc",
+            CompilerPreset::Clang => "clang",
+            CompilerPreset::Zig => "zig cc",
+            CompilerPreset::Tcc => "tcc",
+            CompilerPreset::Icx => "icx",
+        }
+    }
+
+    /// Returns the command compiling C++ sources, or `None` if the driver can't compile C++.
+    fn cpp_command(&self) -> Option<&'static str> {
+        match self {
+            CompilerPreset::Gcc => Some("g++"),
+            CompilerPreset::Clang => Some("clang++"),
+            CompilerPreset::Zig => Some("zig c++"),
+            CompilerPreset::Tcc => None,
+            CompilerPreset::Icx => Some("icpx"),
+        }
+    }
+
+    /// Returns the flags writing a `.d` file with the dependencies of every object, next to it.
+    ///
+    /// tcc doesn't support `-MP`, which adds empty rules for the headers, so deleted headers don't break the build.
+    pub fn dependency_flags(&self) -> &'static str {
+        match self {
+            CompilerPreset::Tcc => "-MD",
+            _ => "-MMD -MP",
+        }
+    }
+
+    /// Returns the flag generating position independent code for shared libraries, if the driver needs one.
+    pub fn pic_flag(&self) -> Option<&'static str> {
+        match self {
+            CompilerPreset::Tcc => None,
+            _ => Some("-fPIC"),
+        }
+    }
+
+    /// Returns the name of the preset, as used on the command line.
+    fn name(&self) -> &'static str {
+        match self {
+            CompilerPreset::Gcc => "gcc",
+            CompilerPreset::Clang => "clang",
+            CompilerPreset::Zig => "zig",
+            CompilerPreset::Tcc => "tcc",
+            CompilerPreset::Icx => "icx",
+        }
+    }
+}
+
+/// Returns the C compiler belonging to a C++ compiler command, e.g. `gcc` for `g++` or `zig cc` for `zig c++`.
+///
+/// # Arguments
+///
+/// * `compiler` - The C++ compiler command.
+///
+/// # Returns
+///
+/// The C compiler command, or `None` if the command isn't a known C++ compiler.
+pub fn get_c_compiler(compiler: &str) -> Option<String> {
+    let compiler = compiler.trim();
+    let (path, name) = compiler.split_at(compiler.rfind('/').map_or(0, |index| index + 1));
+
+    let name = match name {
+        "icpx" => String::from("icx"),
+        _ if name.contains("clang++") => name.replacen("clang++", "clang", 1),
+        _ if name.contains("g++") => name.replacen("g++", "gcc", 1),
+        _ if name.contains("c++") => name.replacen("c++", "cc", 1),
+        _ => return None,
+    };
+
+    Some(format!("{}{}", path, name))
+}
+
+/// A compiler preset, with the target and sanitizers chosen for the Makefile.
+#[derive(PartialEq, Debug, Clone)]
+pub struct CompilerDriver {
+    /// The preset of the compiler.
+    pub preset: CompilerPreset,
+
+    /// The target triple to compile for, e.g. `aarch64-linux-gnu`. `None` for the host.
+    pub target: Option<String>,
+
+    /// The sanitizers to enable, e.g. `address` and `undefined`.
+    pub sanitizers: Vec<String>,
+}
+
+impl CompilerDriver {
+    /// Checks if the driver supports the chosen options and the languages of the sources.
+    ///
+    /// # Arguments
+    ///
+    /// * `has_cpp` - Whether there are C++ sources.
+    /// * `has_host_tools` - Whether there are assembly or Fortran sources, built by `as` and `gfortran` for the host.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` with an error message if something isn't supported.
+    pub fn validate(&self, has_cpp: bool, has_host_tools: bool) -> Result<(), String> {
+        let name = self.preset.name();
+
+        if has_cpp && self.preset.cpp_command().is_none() {
+            return Err(format!("{} can't compile C++ sources", name));
+        }
+        if self.target.is_some() && has_host_tools {
+            return Err(String::from("assembly and Fortran sources can't be compiled for other targets"));
+        }
+        if self.target.is_some() && matches!(self.preset, CompilerPreset::Tcc | CompilerPreset::Icx) {
+            return Err(format!("{} doesn't support compiling for other targets", name));
+        }
+        if !self.sanitizers.is_empty() && self.preset == CompilerPreset::Tcc {
+            return Err(format!("{} doesn't support sanitizers", name));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the command running the compiler, e.g. `zig c++` or `aarch64-linux-gnu-gcc`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cpp` - Whether the compiler has to compile and link C++ sources.
+    pub fn command(&self, cpp: bool) -> String {
+        let command = match cpp {
+            true => self.preset.cpp_command().unwrap_or(self.preset.c_command()),
+            false => self.preset.c_command(),
+        };
+
+        // GCC has a separate driver for every target, prefixed with the target triple
+        match (&self.target, self.preset) {
+            (Some(target), CompilerPreset::Gcc) => format!("{}-{}", target, command),
+            _ => String::from(command),
+        }
+    }
+
+    /// Returns the flags choosing the target, passed when compiling and linking, e.g. `--target=aarch64-linux-gnu`.
+    pub fn target_flags(&self) -> Option<String> {
+        let target = self.target.as_ref()?;
+
+        match self.preset {
+            CompilerPreset::Clang => Some(format!("--target={}", target)),
+            CompilerPreset::Zig => Some(format!("-target {}", target)),
+            _ => None,
+        }
+    }
+
+    /// Returns the flags enabling the sanitizers, passed when compiling and linking, e.g. `-fsanitize=address,undefined`.
+    pub fn sanitize_flags(&self) -> Option<String> {
+        match self.sanitizers.is_empty() {
+            true => None,
+            false => Some(format!("-fsanitize={}", self.sanitizers.join(","))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn driver(preset: CompilerPreset, target: Option<&str>) -> CompilerDriver {
+        CompilerDriver {
+            preset,
+            target: target.map(String::from),
+            sanitizers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn detecting_presets() {
+        assert_eq!(Some(CompilerPreset::Gcc), CompilerPreset::detect("g++"));
+        assert_eq!(Some(CompilerPreset::Gcc), CompilerPreset::detect("aarch64-linux-gnu-gcc"));
+        assert_eq!(Some(CompilerPreset::Clang), CompilerPreset::detect("/usr/bin/clang++-17"));
+        assert_eq!(Some(CompilerPreset::Zig), CompilerPreset::detect("zig cc"));
+        assert_eq!(Some(CompilerPreset::Tcc), CompilerPreset::detect("tcc"));
+        assert_eq!(Some(CompilerPreset::Icx), CompilerPreset::detect("icpx"));
+        assert_eq!(None, CompilerPreset::detect("cl.exe"));
+    }
+
+    #[test]
+    fn getting_commands() {
+        assert_eq!("zig cc", driver(CompilerPreset::Zig, Some("aarch64-linux-gnu")).command(false));
+        assert_eq!("zig c++", driver(CompilerPreset::Zig, None).command(true));
+        assert_eq!("aarch64-linux-gnu-g++", driver(CompilerPreset::Gcc, Some("aarch64-linux-gnu")).command(true));
+        assert_eq!("icpx", driver(CompilerPreset::Icx, None).command(true));
+        assert_eq!("tcc", driver(CompilerPreset::Tcc, None).command(false));
+    }
+
+    #[test]
+    fn getting_c_compilers() {
+        assert_eq!(Some(String::from("gcc")), get_c_compiler("g++"));
+        assert_eq!(Some(String::from("aarch64-linux-gnu-gcc-13")), get_c_compiler("aarch64-linux-gnu-g++-13"));
+        assert_eq!(Some(String::from("/usr/bin/clang-17")), get_c_compiler("/usr/bin/clang++-17"));
+        assert_eq!(Some(String::from("zig cc")), get_c_compiler("zig c++"));
+        assert_eq!(Some(String::from("icx")), get_c_compiler("icpx"));
+        assert_eq!(None, get_c_compiler("gcc"));
+        assert_eq!(None, get_c_compiler("tcc"));
+    }
+
+    #[test]
+    fn getting_pic_flags() {
+        assert_eq!(Some("-fPIC"), CompilerPreset::Clang.pic_flag());
+        assert_eq!(None, CompilerPreset::Tcc.pic_flag());
+    }
+
+    #[test]
+    fn getting_target_flags() {
+        assert_eq!(Some(String::from("-target aarch64-linux-gnu")), driver(CompilerPreset::Zig, Some("aarch64-linux-gnu")).target_flags());
+        assert_eq!(Some(String::from("--target=riscv64-linux-gnu")), driver(CompilerPreset::Clang, Some("riscv64-linux-gnu")).target_flags());
+        assert_eq!(None, driver(CompilerPreset::Gcc, Some("aarch64-linux-gnu")).target_flags());
+        assert_eq!(None, driver(CompilerPreset::Clang, None).target_flags());
+    }
+
+    #[test]
+    fn validating_drivers() {
+        assert!(driver(CompilerPreset::Tcc, None).validate(false, false).is_ok());
+        assert!(driver(CompilerPreset::Tcc, None).validate(true, false).is_err());
+        assert!(driver(CompilerPreset::Icx, Some("aarch64-linux-gnu")).validate(false, false).is_err());
+        assert!(driver(CompilerPreset::Gcc, Some("aarch64-linux-gnu")).validate(true, false).is_ok());
+        assert!(driver(CompilerPreset::Gcc, Some("aarch64-linux-gnu")).validate(false, true).is_err());
+        assert!(driver(CompilerPreset::Gcc, None).validate(false, true).is_ok());
+
+        let sanitized = CompilerDriver {
+            sanitizers: vec![String::from("address")],
+            ..driver(CompilerPreset::Tcc, None)
+        };
+        assert!(sanitized.validate(false, false).is_err());
+        assert_eq!(Some(String::from("-fsanitize=address")), sanitized.sanitize_flags());
+    }
+}
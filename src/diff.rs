@@ -0,0 +1,175 @@
+//! Produces unified diffs between two versions of a Makefile.
+//!
+//! Used to review the changes MakeWiz would make before writing them to disk.
+
+/// Number of unchanged lines shown around every change.
+const CONTEXT: usize = 3;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Edit<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Generates a unified diff between two texts.
+///
+/// # Arguments
+///
+/// * `old` - The current contents.
+/// * `new` - The new contents.
+/// * `old_name` - The name shown in the `---` header line.
+/// * `new_name` - The name shown in the `+++` header line.
+///
+/// # Returns
+///
+/// A `String` containing the diff, or an empty `String` if both texts are the same.
+pub fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let edits = compute_edits(&old_lines, &new_lines);
+
+    let changes: Vec<usize> = (0..edits.len()).filter(|&index| !matches!(edits[index], Edit::Equal(_))).collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // Group changes that are close enough to share their context lines
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &index in &changes {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + 1 + CONTEXT).min(edits.len());
+
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut diff = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for (start, end) in hunks {
+        let old_start = edits[..start].iter().filter(|edit| !matches!(edit, Edit::Insert(_))).count();
+        let new_start = edits[..start].iter().filter(|edit| !matches!(edit, Edit::Delete(_))).count();
+        let old_count = edits[start..end].iter().filter(|edit| !matches!(edit, Edit::Insert(_))).count();
+        let new_count = edits[start..end].iter().filter(|edit| !matches!(edit, Edit::Delete(_))).count();
+
+        diff.push_str(&format!("@@ -{} +{} @@\n", hunk_range(old_start, old_count), hunk_range(new_start, new_count)));
+
+        for edit in &edits[start..end] {
+            match edit {
+                Edit::Equal(line) => diff.push_str(&format!(" {}\n", line)),
+                Edit::Delete(line) => diff.push_str(&format!("-{}\n", line)),
+                Edit::Insert(line) => diff.push_str(&format!("+{}\n", line)),
+            }
+        }
+    }
+
+    diff
+}
+
+fn hunk_range(start: usize, count: usize) -> String {
+    // Empty ranges point at the line before them
+    let first_line = if count == 0 { start } else { start + 1 };
+
+    format!("{},{}", first_line, count)
+}
+
+/// Computes the shortest edit script using the longest common subsequence of lines.
+fn compute_edits<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Edit<'a>> {
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            edits.push(Edit::Equal(old[i]));
+            i += 1;
+            j += 1;
+        }
+        else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            edits.push(Edit::Delete(old[i]));
+            i += 1;
+        }
+        else {
+            edits.push(Edit::Insert(new[j]));
+            j += 1;
+        }
+    }
+    edits.extend(old[i..].iter().map(|line| Edit::Delete(line)));
+    edits.extend(new[j..].iter().map(|line| Edit::Insert(line)));
+
+    edits
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn diff_of_same_texts_is_empty() {
+        assert_eq!("", unified_diff("a\nb\n", "a\nb\n", "old", "new"));
+    }
+
+    #[test]
+    fn diff_with_single_change() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n";
+        let expected = "\
+--- old
++++ new
+@@ -2,7 +2,7 @@
+ 2
+ 3
+ 4
+-5
++five
+ 6
+ 7
+ 8
+";
+
+        assert_eq!(expected, unified_diff(old, new, "old", "new"));
+    }
+
+    #[test]
+    fn diff_against_empty_text() {
+        let expected = "\
+--- old
++++ new
+@@ -0,0 +1,2 @@
++a
++b
+";
+
+        assert_eq!(expected, unified_diff("", "a\nb\n", "old", "new"));
+    }
+
+    #[test]
+    fn distant_changes_are_split_into_hunks() {
+        let old = "a\n1\n2\n3\n4\n5\n6\n7\nb\n";
+        let new = "A\n1\n2\n3\n4\n5\n6\n7\nB\n";
+        let expected = "\
+--- old
++++ new
+@@ -1,4 +1,4 @@
+-a
++A
+ 1
+ 2
+ 3
+@@ -6,4 +6,4 @@
+ 5
+ 6
+ 7
+-b
++B
+";
+
+        assert_eq!(expected, unified_diff(old, new, "old", "new"));
+    }
+}
@@ -0,0 +1,1161 @@
+//! Handles data required for generating a Java Makefile.
+//!
+//! It scans the source tree for Java files, reads their `package` declarations and
+//! computes where `javac` will place their compiled classes (.class) in the output directory.
+//! Test classes (JUnit) are kept separately, so they can be compiled against the main classes.
+//! Projects with `module-info.java` files are compiled as Java modules (JPMS).
+//! Resources (e.g. `.properties` files or images) are collected, so they can be copied next to the classes.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::StringVector;
+
+/// Directory used as the source root, if it exists.
+pub const SOURCE_DIR: &str = "src";
+
+/// Directory where `javac` places the compiled classes.
+pub const OUTPUT_DIR: &str = "out";
+
+/// Directory searched for dependency JARs.
+pub const LIBRARY_DIR: &str = "lib";
+
+/// Directory containing test sources, if it exists.
+pub const TEST_DIR: &str = "test";
+
+/// Directory where `javac` places the compiled test classes.
+pub const TEST_OUTPUT_DIR: &str = "out-test";
+
+/// Directory where `jar` places the modular jars of modular projects, one per module.
+pub const JAR_OUTPUT_DIR: &str = "out-jars";
+
+/// Directory containing resources, if it exists.
+pub const RESOURCE_DIR: &str = "resources";
+
+/// Directory where `javadoc` places the generated documentation.
+pub const DOC_DIR: &str = "out-doc";
+
+/// Default path of the JUnit console launcher JAR.
+pub const JUNIT_JAR: &str = "lib/junit-platform-console-standalone.jar";
+
+/// A single Java, Kotlin or Scala source file.
+#[derive(PartialEq, Debug)]
+pub struct JavaSource {
+    /// Path to the source file, e.g. `src/com/example/App.java`.
+    pub path: String,
+
+    /// The declared package, e.g. `com.example`. Empty for the default package.
+    pub package: String,
+
+    /// The name of the top-level class, taken from the file name.
+    /// For Kotlin, the class holding the top-level functions, e.g. `AppKt` for `App.kt`.
+    /// For Scala, the object with the entry point, if there is one.
+    pub class_name: String,
+
+    /// Whether the file declares a `public static void main(String[])` method,
+    /// a top-level `main` function in Kotlin, or an entry point object in Scala.
+    pub has_main_method: bool,
+
+    /// Whether the file contains JUnit `@Test` annotations.
+    pub has_test_annotations: bool,
+
+    /// The name of the module the file belongs to. Empty outside of modular projects.
+    pub module: String,
+
+    /// Names of the native libraries loaded with `System.loadLibrary`.
+    pub loaded_libraries: Vec<String>,
+}
+
+impl JavaSource {
+    /// Returns the fully qualified class name, e.g. `com.example.App`.
+    pub fn qualified_name(&self) -> String {
+        if self.package.is_empty() {
+            self.class_name.clone()
+        }
+        else {
+            format!("{}.{}", self.package, self.class_name)
+        }
+    }
+
+    /// Checks if this is a test class that should be run by JUnit.
+    pub fn is_test_class(&self) -> bool {
+        self.has_test_annotations || self.class_name.ends_with("Test")
+    }
+
+    /// Returns the path of the compiled class in the given output directory, e.g. `out/com/example/App.class`.
+    /// Classes of a module are placed in a directory named after the module, e.g. `out/com.example/com/example/App.class`.
+    pub fn compiled_file(&self, output_dir: &str) -> String {
+        let class_path = self.qualified_name().replace('.', "/");
+
+        match self.module.is_empty() {
+            true => format!("{}/{}.class", output_dir, class_path),
+            false => format!("{}/{}/{}.class", output_dir, self.module, class_path),
+        }
+    }
+}
+
+/// A Java module, declared by a `module-info.java` file.
+#[derive(PartialEq, Debug)]
+pub struct JavaModule {
+    /// The module name, e.g. `com.example.app`.
+    pub name: String,
+
+    /// The directory containing `module-info.java`, e.g. `src/com.example.app`.
+    pub root: String,
+
+    /// Names of the modules listed in `requires` directives.
+    pub requires: Vec<String>,
+}
+
+/// Source files packaged into a sources jar.
+#[derive(PartialEq, Debug)]
+pub struct PackagedSources {
+    /// The name of the sources jar, e.g. `App-sources.jar`.
+    pub jar_name: String,
+
+    /// The directory the source files are packaged from, e.g. `src`.
+    pub root: String,
+
+    /// The source files, relative to `root`, so they're laid out by package.
+    pub files: StringVector,
+}
+
+/// A struct that holds data for generating a Java Makefile.
+#[derive(PartialEq, Debug)]
+pub struct JavaData {
+    /// The name of the project directory, used for naming artifacts without a main class.
+    pub project_name: String,
+
+    /// The source root passed to `javac -sourcepath`.
+    pub source_dir: String,
+
+    /// The directory passed to `javac -d`.
+    pub output_dir: String,
+
+    /// Collection of Java source files, sorted by path.
+    pub sources: Vec<JavaSource>,
+
+    /// Collection of Kotlin source files, sorted by path. Only scanned for Kotlin projects.
+    pub kotlin_sources: Vec<JavaSource>,
+
+    /// Collection of Scala source files, sorted by path. Only scanned for Scala projects.
+    pub scala_sources: Vec<JavaSource>,
+
+    /// The fully qualified name of the class run by the `run` target and the executable jar.
+    pub main_class: Option<String>,
+
+    /// Classpath entries (JARs and directories) used for compiling, running and packaging.
+    /// In modular projects, they're used as the module path instead.
+    pub classpath: Vec<String>,
+
+    /// Modules declared in the source tree, sorted by name. Empty for non-modular projects.
+    pub modules: Vec<JavaModule>,
+
+    /// The directory with resources copied into the output directory.
+    pub resource_dir: Option<String>,
+
+    /// Resource files in the resource directory, e.g. `resources/app.properties`, sorted by path.
+    pub resources: Vec<String>,
+
+    /// Collection of test sources, from the `test/` directory or annotated with `@Test`.
+    pub test_sources: Vec<JavaSource>,
+
+    /// The JUnit console launcher JAR used by the `test` target.
+    pub junit_jar: Option<String>,
+
+    /// The Java release passed to `javac --release`. Empty for the javac default.
+    pub release: String,
+
+    /// The lint profile passed to `javac -Xlint`. Empty to disable it.
+    pub lint: String,
+
+    /// Whether `javac` should treat warnings as errors.
+    pub werror: bool,
+
+    /// The source file encoding passed to `javac -encoding`. Empty for the javac default.
+    pub encoding: String,
+
+    /// Additional flags passed to `javac`.
+    pub extra_flags: String,
+
+    /// Whether to add a `doc` target generating the documentation with `javadoc`.
+    pub doc: bool,
+
+    /// Whether to add a `sources-jar` target packaging the source files.
+    pub sources_jar: bool,
+}
+
+impl JavaData {
+    /// Scans the given project directory for Java source files and dependency JARs.
+    ///
+    /// If the project has a `src/` directory, it's used as the source root.
+    /// Otherwise the whole project directory is scanned. Hidden directories and
+    /// the output directories are always skipped. JARs found in `lib/` are added to the classpath,
+    /// except for the JUnit console launcher, which is only used for running tests.
+    ///
+    /// Files in the `test/` directory and files with `@Test` annotations are treated as test sources.
+    /// Every `module-info.java` in the source root declares a module, containing the sources below it.
+    /// Files in the `resources/` directory are used as resources.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The project directory to scan.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the extracted `JavaData` or an error.
+    pub fn extract_sources(root: &Path) -> Result<JavaData, Box<dyn std::error::Error>> {
+        let source_dir = if root.join(SOURCE_DIR).is_dir() { SOURCE_DIR } else { "." };
+
+        let project_name = root.canonicalize()?.file_name()
+            .map_or(String::from("project"), |name| name.to_string_lossy().to_string());
+
+        let mut data = JavaData {
+            project_name,
+            source_dir: String::from(source_dir),
+            output_dir: String::from(OUTPUT_DIR),
+            sources: Vec::new(),
+            kotlin_sources: Vec::new(),
+            scala_sources: Vec::new(),
+            main_class: None,
+            classpath: Vec::new(),
+            modules: Vec::new(),
+            resource_dir: None,
+            resources: Vec::new(),
+            test_sources: Vec::new(),
+            junit_jar: None,
+            release: String::new(),
+            lint: String::new(),
+            werror: false,
+            encoding: String::new(),
+            extra_flags: String::new(),
+            doc: false,
+            sources_jar: false,
+        };
+
+        let skipped_dirs = [root.join(OUTPUT_DIR), root.join(TEST_OUTPUT_DIR), root.join(TEST_DIR), root.join(RESOURCE_DIR)];
+        let mut sources = read_sources(root, &root.join(source_dir), &skipped_dirs)?;
+
+        for source in sources.iter().filter(|source| source.class_name == "module-info") {
+            let code = strip_comments(&fs::read_to_string(root.join(&source.path))?);
+
+            if let Some((name, requires)) = read_module_declaration(&code) {
+                let root = Path::new(&source.path).parent().unwrap().to_string_lossy().to_string();
+                let root = if root.is_empty() { String::from(".") } else { root };
+
+                data.modules.push(JavaModule { name, root, requires });
+            }
+        }
+        data.modules.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for mut source in sources.drain(..) {
+            source.module = data.find_module(&source.path).unwrap_or_default();
+
+            match source.has_test_annotations {
+                true => data.test_sources.push(source),
+                false => data.sources.push(source),
+            }
+        }
+
+        if root.join(TEST_DIR).is_dir() {
+            data.test_sources.extend(read_sources(root, &root.join(TEST_DIR), &skipped_dirs)?);
+        }
+
+        data.sources.sort_by(|a, b| a.path.cmp(&b.path));
+        data.test_sources.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let library_dir = root.join(LIBRARY_DIR);
+        if library_dir.is_dir() {
+            for entry in fs::read_dir(library_dir)? {
+                let name = entry?.file_name().to_string_lossy().to_string();
+                let path = format!("{}/{}", LIBRARY_DIR, name);
+
+                if name.starts_with("junit-platform-console-standalone") && name.ends_with(".jar") {
+                    data.junit_jar = Some(path);
+                }
+                else if name.ends_with(".jar") {
+                    data.classpath.push(path);
+                }
+            }
+            data.classpath.sort();
+        }
+
+        if root.join(RESOURCE_DIR).is_dir() {
+            data.set_resource_dir(root, RESOURCE_DIR)?;
+        }
+
+        Ok(data)
+    }
+
+    /// Scans the source root of the given project directory for Kotlin source files (.kt),
+    /// skipping the same directories as [`JavaData::extract_sources`].
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The project directory to scan.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating whether the sources could be read.
+    pub fn extract_kotlin_sources(&mut self, root: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let skipped_dirs = [root.join(OUTPUT_DIR), root.join(TEST_OUTPUT_DIR), root.join(TEST_DIR), root.join(RESOURCE_DIR)];
+
+        self.kotlin_sources = read_kotlin_sources(root, &root.join(&self.source_dir), &skipped_dirs)?;
+        self.kotlin_sources.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(())
+    }
+
+    /// Scans the source root of the given project directory for Scala source files (.scala),
+    /// skipping the same directories as [`JavaData::extract_sources`].
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The project directory to scan.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating whether the sources could be read.
+    pub fn extract_scala_sources(&mut self, root: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let skipped_dirs = [root.join(OUTPUT_DIR), root.join(TEST_OUTPUT_DIR), root.join(TEST_DIR), root.join(RESOURCE_DIR)];
+
+        self.scala_sources = read_scala_sources(root, &root.join(&self.source_dir), &skipped_dirs)?;
+        self.scala_sources.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(())
+    }
+
+    /// Sets the resource directory and collects the files in it, skipping hidden files and directories.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The project directory.
+    /// * `dir` - The resource directory, relative to the project directory.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating whether the directory could be read.
+    pub fn set_resource_dir(&mut self, root: &Path, dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = dir.trim_end_matches('/');
+
+        let mut resources = Vec::new();
+        collect_files(root, &root.join(dir), &[], &|_| true, &mut resources)?;
+        resources.sort();
+
+        self.resource_dir = Some(String::from(dir));
+        self.resources = resources;
+
+        Ok(())
+    }
+
+    /// Generates the javac flags variables, as `(name, value)` pairs, for the options that are set.
+    pub fn get_javac_flags(&self) -> Vec<(&'static str, String)> {
+        let mut flags = Vec::new();
+
+        if !self.release.is_empty() {
+            flags.push(("JRELEASE", format!("--release {}", self.release)));
+        }
+
+        let mut lint_flags = Vec::new();
+        if !self.lint.is_empty() {
+            lint_flags.push(format!("-Xlint:{}", self.lint));
+        }
+        if self.werror {
+            lint_flags.push(String::from("-Werror"));
+        }
+        if !lint_flags.is_empty() {
+            flags.push(("JLINT", lint_flags.join(" ")));
+        }
+
+        if !self.encoding.is_empty() {
+            flags.push(("JENCODING", format!("-encoding {}", self.encoding)));
+        }
+        if !self.extra_flags.is_empty() {
+            flags.push(("JEXTRAFLAGS", self.extra_flags.clone()));
+        }
+
+        flags
+    }
+
+    /// Adds entries to the classpath, skipping the ones that are already in it.
+    pub fn add_to_classpath(&mut self, entries: &[String]) {
+        for entry in entries {
+            if !self.classpath.contains(entry) {
+                self.classpath.push(entry.clone());
+            }
+        }
+    }
+
+    /// Finds the name of the module containing the given source file, i.e. the one with the deepest root above it.
+    fn find_module(&self, path: &str) -> Option<String> {
+        self.modules.iter()
+            .filter(|module| module.root == "." || Path::new(path).starts_with(&module.root))
+            .max_by_key(|module| if module.root == "." { 0 } else { module.root.len() })
+            .map(|module| module.name.clone())
+    }
+
+    /// Checks if the project is compiled as Java modules.
+    pub fn is_modular(&self) -> bool {
+        !self.modules.is_empty()
+    }
+
+    /// Get the module containing the main class, falling back to the first module.
+    pub fn get_main_module(&self) -> Option<String> {
+        let main_class = self.main_class.as_ref()?;
+
+        self.sources.iter()
+            .find(|source| &source.qualified_name() == main_class && !source.module.is_empty())
+            .map(|source| source.module.clone())
+            .or_else(|| self.modules.first().map(|module| module.name.clone()))
+    }
+
+    /// Get the module packaged into jars, i.e. the module containing the main class or the first module.
+    pub fn get_primary_module(&self) -> Option<&JavaModule> {
+        let name = self.get_main_module();
+
+        self.modules.iter()
+            .find(|module| Some(&module.name) == name.as_ref())
+            .or(self.modules.first())
+    }
+
+    /// Get the base name of the jar files, i.e. the simple name of the main class, the name of
+    /// the primary module or the project name.
+    pub fn get_artifact_name(&self) -> String {
+        if let Some(main_class) = &self.main_class {
+            return main_class.rsplit('.').next().unwrap_or(main_class).to_string();
+        }
+
+        match self.get_primary_module() {
+            Some(module) => module.name.clone(),
+            None => self.project_name.clone(),
+        }
+    }
+
+    /// Get the packages of the sources, sorted and without duplicates.
+    /// Returns `None` if some sources are in the default package, which `javadoc` can't select by name.
+    pub fn get_packages(&self) -> Option<StringVector> {
+        let mut packages = Vec::new();
+
+        for source in self.sources.iter().filter(|source| source.class_name != "module-info") {
+            if source.package.is_empty() {
+                return None;
+            }
+            packages.push(source.package.clone());
+        }
+
+        packages.sort();
+        packages.dedup();
+        Some(StringVector(packages))
+    }
+
+    /// Get the source files packaged into sources jars, with the directories they're packaged from.
+    /// Modular projects get a sources jar for every module, packaged from the root of the module,
+    /// since every module has its own `module-info.java`.
+    pub fn get_packaged_sources(&self) -> Vec<PackagedSources> {
+        if self.modules.is_empty() {
+            return vec![self.get_module_sources(format!("{}-sources.jar", self.get_artifact_name()), &self.source_dir, "")];
+        }
+
+        self.modules.iter()
+            .map(|module| self.get_module_sources(format!("{}-sources.jar", module.name), &module.root, &module.name))
+            .collect()
+    }
+
+    /// Get the source files of the given module, relative to its root. The module is empty outside of modular projects.
+    fn get_module_sources(&self, jar_name: String, root: &str, module: &str) -> PackagedSources {
+        let files = self.sources.iter()
+            .filter(|source| source.module == module)
+            .map(|source| {
+                let path = Path::new(&source.path);
+                path.strip_prefix(root).unwrap_or(path).to_string_lossy().to_string()
+            })
+            .collect();
+
+        PackagedSources {
+            jar_name,
+            root: root.to_string(),
+            files: StringVector(files),
+        }
+    }
+
+    /// Get the values passed to `javac --module-source-path`.
+    ///
+    /// If every module lives in a directory named after it, under a common parent directory,
+    /// the parent is used as a single module source path. Otherwise each module gets
+    /// its own `name=root` entry.
+    pub fn get_module_source_path(&self) -> StringVector {
+        let parents: Vec<Option<&Path>> = self.modules.iter()
+            .map(|module| {
+                let root = Path::new(&module.root);
+                match root.file_name() {
+                    Some(name) if name.to_string_lossy() == module.name => root.parent(),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        if let Some(Some(parent)) = parents.first() {
+            if parents.iter().all(|other| other == &Some(*parent)) {
+                let parent = parent.to_string_lossy().to_string();
+                return StringVector(vec![if parent.is_empty() { String::from(".") } else { parent }]);
+            }
+        }
+
+        StringVector(self.modules.iter().map(|module| format!("{}={}", module.name, module.root)).collect())
+    }
+
+    /// Get the required modules that are neither declared in the project nor part of the JDK.
+    pub fn get_external_requires(&self) -> Vec<String> {
+        let mut requires: Vec<String> = self.modules.iter()
+            .flat_map(|module| module.requires.iter())
+            .filter(|name| !name.starts_with("java.") && !name.starts_with("jdk."))
+            .filter(|name| !self.modules.iter().any(|module| &&module.name == name))
+            .cloned()
+            .collect();
+
+        requires.sort();
+        requires.dedup();
+        requires
+    }
+
+    /// Get the names of the native libraries loaded by the sources, sorted and without duplicates.
+    pub fn get_loaded_libraries(&self) -> Vec<String> {
+        let mut libraries: Vec<String> = self.sources.iter()
+            .flat_map(|source| source.loaded_libraries.iter().cloned())
+            .collect();
+
+        libraries.sort();
+        libraries.dedup();
+        libraries
+    }
+
+    /// Get the fully qualified names of all classes with a main method, including the Kotlin and Scala sources.
+    pub fn get_main_classes(&self) -> Vec<String> {
+        self.sources.iter()
+            .chain(self.kotlin_sources.iter())
+            .chain(self.scala_sources.iter())
+            .filter(|source| source.has_main_method)
+            .map(|source| source.qualified_name())
+            .collect()
+    }
+
+    /// Checks if the given fully qualified name is one of the classes with a main method.
+    pub fn has_main_class(&self, name: &str) -> bool {
+        self.get_main_classes().iter().any(|main_class| main_class == name)
+    }
+
+    /// Get Java source files (.java).
+    pub fn get_source_files(&self) -> StringVector {
+        StringVector(self.sources.iter().map(|source| source.path.clone()).collect())
+    }
+
+    /// Get Kotlin source files (.kt).
+    pub fn get_kotlin_source_files(&self) -> StringVector {
+        StringVector(self.kotlin_sources.iter().map(|source| source.path.clone()).collect())
+    }
+
+    /// Get Scala source files (.scala).
+    pub fn get_scala_source_files(&self) -> StringVector {
+        StringVector(self.scala_sources.iter().map(|source| source.path.clone()).collect())
+    }
+
+    /// Get Java compiled files (.class) in the output directory.
+    pub fn get_compiled_files(&self) -> StringVector {
+        StringVector(self.sources.iter().map(|source| source.compiled_file(&self.output_dir)).collect())
+    }
+
+    /// Get the resource files copied into the output directory, e.g. `out/app.properties`.
+    /// In modular projects, they're copied into the directory of the main module.
+    pub fn get_copied_resource_files(&self) -> StringVector {
+        let Some(resource_dir) = &self.resource_dir else {
+            return StringVector::new();
+        };
+        let output_dir = self.get_resource_output_dir();
+
+        StringVector(self.resources.iter()
+            .map(|path| {
+                let relative_path = Path::new(path).strip_prefix(resource_dir).unwrap_or(Path::new(path));
+                format!("{}/{}", output_dir, relative_path.to_string_lossy())
+            })
+            .collect())
+    }
+
+    /// Get the directory the resources are copied into.
+    pub fn get_resource_output_dir(&self) -> String {
+        match self.get_primary_module() {
+            Some(module) => format!("{}/{}", self.output_dir, module.name),
+            None => self.output_dir.clone(),
+        }
+    }
+
+    /// Get Java test source files (.java).
+    pub fn get_test_source_files(&self) -> StringVector {
+        StringVector(self.test_sources.iter().map(|source| source.path.clone()).collect())
+    }
+
+    /// Get the fully qualified names of the test classes run by JUnit.
+    pub fn get_test_classes(&self) -> StringVector {
+        StringVector(self.test_sources.iter()
+            .filter(|source| source.is_test_class())
+            .map(|source| source.qualified_name())
+            .collect())
+    }
+}
+
+/// Reads all Java sources in `dir`, skipping the given directories.
+fn read_sources(root: &Path, dir: &Path, skipped_dirs: &[PathBuf]) -> Result<Vec<JavaSource>, Box<dyn std::error::Error>> {
+    let mut paths = Vec::new();
+    collect_files(root, dir, skipped_dirs, &|name| name.ends_with(".java"), &mut paths)?;
+
+    let mut sources = Vec::new();
+    for relative_path in paths {
+        let code = strip_comments(&fs::read_to_string(root.join(&relative_path))?);
+        let class_name = Path::new(&relative_path).file_stem().unwrap().to_str().unwrap().to_string();
+
+        sources.push(JavaSource {
+            path: relative_path,
+            package: read_package(&code),
+            class_name,
+            has_main_method: has_main_method(&code),
+            has_test_annotations: has_test_annotations(&code),
+            module: String::new(),
+            loaded_libraries: read_loaded_libraries(&code),
+        });
+    }
+
+    Ok(sources)
+}
+
+/// Reads all Kotlin sources in `dir`, skipping the given directories.
+fn read_kotlin_sources(root: &Path, dir: &Path, skipped_dirs: &[PathBuf]) -> Result<Vec<JavaSource>, Box<dyn std::error::Error>> {
+    let mut paths = Vec::new();
+    collect_files(root, dir, skipped_dirs, &|name| name.ends_with(".kt"), &mut paths)?;
+
+    let mut sources = Vec::new();
+    for relative_path in paths {
+        let code = strip_comments(&fs::read_to_string(root.join(&relative_path))?);
+        let stem = Path::new(&relative_path).file_stem().unwrap().to_str().unwrap();
+
+        // Top-level functions are compiled into a class named after the file, e.g. AppKt
+        let mut chars = stem.chars();
+        let class_name = match chars.next() {
+            Some(first) => format!("{}{}Kt", first.to_uppercase(), chars.as_str()),
+            None => String::from("Kt"),
+        };
+
+        sources.push(JavaSource {
+            path: relative_path,
+            package: read_package(&code),
+            class_name,
+            has_main_method: has_kotlin_main_function(&code),
+            has_test_annotations: false,
+            module: String::new(),
+            loaded_libraries: Vec::new(),
+        });
+    }
+
+    Ok(sources)
+}
+
+/// Reads all Scala sources in `dir`, skipping the given directories.
+fn read_scala_sources(root: &Path, dir: &Path, skipped_dirs: &[PathBuf]) -> Result<Vec<JavaSource>, Box<dyn std::error::Error>> {
+    let mut paths = Vec::new();
+    collect_files(root, dir, skipped_dirs, &|name| name.ends_with(".scala"), &mut paths)?;
+
+    let mut sources = Vec::new();
+    for relative_path in paths {
+        let code = strip_comments(&fs::read_to_string(root.join(&relative_path))?);
+        let main_object = read_scala_main_object(&code);
+        let class_name = match &main_object {
+            Some(name) => name.clone(),
+            None => Path::new(&relative_path).file_stem().unwrap().to_str().unwrap().to_string(),
+        };
+
+        sources.push(JavaSource {
+            path: relative_path,
+            package: read_package(&code),
+            class_name,
+            has_main_method: main_object.is_some(),
+            has_test_annotations: false,
+            module: String::new(),
+            loaded_libraries: Vec::new(),
+        });
+    }
+
+    Ok(sources)
+}
+
+/// Recursively collects paths of files in `dir` whose names match the filter, relative to `root`.
+fn collect_files(root: &Path, dir: &Path, skipped_dirs: &[PathBuf], filter: &dyn Fn(&str) -> bool, paths: &mut Vec<String>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            if !name.starts_with('.') && !skipped_dirs.contains(&path) {
+                collect_files(root, &path, skipped_dirs, filter, paths)?;
+            }
+        }
+        else if file_type.is_file() && !name.starts_with('.') && filter(&name) {
+            let relative_path = path.strip_prefix(root).unwrap_or(&path);
+            let relative_path = relative_path.strip_prefix(".").unwrap_or(relative_path);
+            paths.push(relative_path.to_string_lossy().to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes `//` and `/* */` comments from Java source code.
+fn strip_comments(contents: &str) -> String {
+    let mut stripped = String::with_capacity(contents.len());
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    // Keeps line breaks, so the code after the comment stays on its own line
+                    if c == '\n' {
+                        stripped.push('\n');
+                    }
+                    previous = c;
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+
+    stripped
+}
+
+/// Reads the `package` declaration from comment-free Java source code.
+fn read_package(code: &str) -> String {
+    for line in code.lines() {
+        let line = line.trim();
+
+        if let Some(rest) = line.strip_prefix("package") {
+            if rest.starts_with(char::is_whitespace) {
+                return rest.split(';').next().unwrap_or("").trim().to_string();
+            }
+        }
+    }
+
+    String::new()
+}
+
+/// Reads the module name and the `requires` directives from a comment-free `module-info.java`.
+fn read_module_declaration(code: &str) -> Option<(String, Vec<String>)> {
+    let code = code.replace('{', " { ").replace('}', " } ").replace(';', " ; ");
+    let mut tokens = code.split_whitespace();
+
+    tokens.by_ref().find(|&token| token == "module")?;
+    let name = tokens.next()?.to_string();
+    if tokens.next() != Some("{") {
+        return None;
+    }
+
+    // Modifiers like `transitive` and `static` come before the module name, so it's the last word of the directive
+    let mut requires = Vec::new();
+    let mut directive: Vec<&str> = Vec::new();
+    for token in tokens {
+        match token {
+            ";" | "}" => {
+                if directive.first() == Some(&"requires") && directive.len() > 1 {
+                    requires.push(directive[directive.len() - 1].to_string());
+                }
+                directive.clear();
+            }
+            _ => directive.push(token),
+        }
+    }
+
+    Some((name, requires))
+}
+
+/// Reads the names of the libraries loaded with `System.loadLibrary("name")` from comment-free Java source code.
+fn read_loaded_libraries(code: &str) -> Vec<String> {
+    code.match_indices("loadLibrary").filter_map(|(index, _)| {
+        let arguments = code[index + "loadLibrary".len()..].trim_start().strip_prefix('(')?.trim_start();
+        let name = arguments.strip_prefix('"')?.split('"').next()?;
+
+        (!name.is_empty()).then(|| name.to_string())
+    })
+    .collect()
+}
+
+/// Checks if comment-free Java source code declares a `public static void main(String[])` method.
+fn has_main_method(code: &str) -> bool {
+    let code: String = code.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    code.match_indices("void main").any(|(index, _)| {
+        let declaration = &code[..index];
+        let parameters = code[index + "void main".len()..].trim_start();
+        let modifiers = &declaration[declaration.rfind(['{', '}', ';']).map_or(0, |brace| brace + 1)..];
+
+        modifiers.contains("static") && modifiers.contains("public")
+            && (parameters.starts_with("(String") || parameters.starts_with("(final String"))
+    })
+}
+
+/// Checks if comment-free Kotlin source code declares a top-level `main` function.
+fn has_kotlin_main_function(code: &str) -> bool {
+    let mut depth = 0;
+
+    for line in code.lines() {
+        let declares_main = line.match_indices("fun ").any(|(index, _)| {
+            let name = line[index + "fun ".len()..].trim_start();
+            name.strip_prefix("main").is_some_and(|parameters| parameters.trim_start().starts_with('('))
+        });
+
+        // Functions declared inside classes and objects aren't entry points
+        if declares_main && depth == 0 {
+            return true;
+        }
+
+        depth += line.matches('{').count() as i64 - line.matches('}').count() as i64;
+    }
+
+    false
+}
+
+/// Reads the name of the entry point from comment-free Scala source code: an `object` extending `App`,
+/// an `object` declaring a `def main(...)` method, or a Scala 3 `@main def` function.
+///
+/// A `def main` only counts directly in the body of an object, which is tracked by brace depth.
+/// Bodies of Scala 3 objects without braces (`object Main:`) end at the next definition on the same depth.
+fn read_scala_main_object(code: &str) -> Option<String> {
+    let code = code.replace('{', " { ").replace('}', " } ").replace('(', " ( ").replace(':', " : ").replace('[', " [ ");
+    let tokens: Vec<&str> = code.split_whitespace().collect();
+
+    // Objects enclosing the current token, with the depth of their body and whether it's indentation-based
+    let mut objects: Vec<(&str, usize, bool)> = Vec::new();
+    let mut declared_object = None;
+    let mut depth = 0;
+
+    for (index, &token) in tokens.iter().enumerate() {
+        let next = tokens.get(index + 1).copied();
+
+        match token {
+            "object" | "class" | "trait" => {
+                objects.retain(|&(_, body_depth, indented)| !indented || body_depth != depth);
+                declared_object = next.filter(|_| token == "object");
+
+                // Parents are listed after `extends` and `with`, before the body
+                let declaration: Vec<&str> = tokens[index + 1..].iter()
+                    .take_while(|token| !matches!(**token, "{" | ":"))
+                    .copied()
+                    .collect();
+                if token == "object" && declaration.windows(2).any(|pair| matches!(pair[0], "extends" | "with") && pair[1] == "App") {
+                    return declared_object.map(String::from);
+                }
+            }
+            "{" => {
+                depth += 1;
+                if let Some(name) = declared_object.take() {
+                    objects.push((name, depth, false));
+                }
+            }
+            "}" => {
+                depth = depth.saturating_sub(1);
+                objects.retain(|&(_, body_depth, indented)| indented || body_depth <= depth);
+            }
+            ":" => {
+                if let Some(name) = declared_object.take() {
+                    objects.push((name, depth, true));
+                }
+            }
+            "@main" if next == Some("def") => return tokens.get(index + 2).map(|name| name.to_string()),
+            "def" if next == Some("main") && tokens.get(index + 2) == Some(&"(") => {
+                if let Some(&(name, body_depth, _)) = objects.last() {
+                    if body_depth == depth {
+                        return Some(name.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Checks if comment-free Java source code contains JUnit test annotations.
+fn has_test_annotations(code: &str) -> bool {
+    const ANNOTATIONS: [&str; 4] = ["@Test", "@ParameterizedTest", "@RepeatedTest", "@TestFactory"];
+
+    ANNOTATIONS.iter().any(|annotation| {
+        code.match_indices(annotation).any(|(index, _)| {
+            !code[index + annotation.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract_sources_with_packages() {
+        let root = Path::new("./test-dirs/test-java-package-layout");
+        let result = JavaData::extract_sources(root).unwrap();
+
+        let expected = JavaData {
+            project_name: String::from("test-java-package-layout"),
+            source_dir: String::from("src"),
+            output_dir: String::from("out"),
+            sources: vec![
+                JavaSource {
+                    path: String::from("src/com/example/App.java"),
+                    package: String::from("com.example"),
+                    class_name: String::from("App"),
+                    has_main_method: true,
+                    has_test_annotations: false,
+                    module: String::new(),
+                    loaded_libraries: Vec::new(),
+                },
+                JavaSource {
+                    path: String::from("src/com/example/util/Helper.java"),
+                    package: String::from("com.example.util"),
+                    class_name: String::from("Helper"),
+                    has_main_method: false,
+                    has_test_annotations: false,
+                    module: String::new(),
+                    loaded_libraries: Vec::new(),
+                },
+            ],
+            kotlin_sources: Vec::new(),
+            scala_sources: Vec::new(),
+            main_class: None,
+            classpath: vec![String::from("lib/dependency.jar")],
+            modules: Vec::new(),
+            resource_dir: Some(String::from("resources")),
+            resources: vec![String::from("resources/app.properties"), String::from("resources/images/logo.txt")],
+            test_sources: vec![
+                JavaSource {
+                    path: String::from("test/com/example/AppTest.java"),
+                    package: String::from("com.example"),
+                    class_name: String::from("AppTest"),
+                    has_main_method: false,
+                    has_test_annotations: true,
+                    module: String::new(),
+                    loaded_libraries: Vec::new(),
+                },
+                JavaSource {
+                    path: String::from("test/com/example/TestUtils.java"),
+                    package: String::from("com.example"),
+                    class_name: String::from("TestUtils"),
+                    has_main_method: false,
+                    has_test_annotations: false,
+                    module: String::new(),
+                    loaded_libraries: Vec::new(),
+                },
+            ],
+            junit_jar: Some(String::from("lib/junit-platform-console-standalone-1.10.0.jar")),
+            release: String::new(),
+            lint: String::new(),
+            werror: false,
+            encoding: String::new(),
+            extra_flags: String::new(),
+            doc: false,
+            sources_jar: false,
+        };
+
+        assert_eq!(expected, result);
+        assert_eq!(vec![String::from("com.example.App")], result.get_main_classes());
+        assert!(result.has_main_class("com.example.App"));
+        assert!(!result.has_main_class("com.example.Ap"));
+        assert!(!result.has_main_class("com.example.util.Helper"));
+        assert_eq!(StringVector(vec![String::from("com.example.AppTest")]), result.get_test_classes());
+        assert_eq!(StringVector(vec![String::from("out/com/example/App.class"), String::from("out/com/example/util/Helper.class")]),
+            result.get_compiled_files());
+        assert_eq!(StringVector(vec![String::from("out/app.properties"), String::from("out/images/logo.txt")]),
+            result.get_copied_resource_files());
+        assert_eq!(Some(StringVector(vec![String::from("com.example"), String::from("com.example.util")])),
+            result.get_packages());
+        assert_eq!(vec![PackagedSources {
+            jar_name: String::from("test-java-package-layout-sources.jar"),
+            root: String::from("src"),
+            files: StringVector(vec![String::from("com/example/App.java"), String::from("com/example/util/Helper.java")]),
+        }], result.get_packaged_sources());
+        assert_eq!("test-java-package-layout", result.get_artifact_name());
+    }
+
+    #[test]
+    fn extract_sources_without_source_dir() {
+        let root = Path::new("./test-dirs/test-java-makefile-creation");
+        let result = JavaData::extract_sources(root).unwrap();
+
+        assert_eq!(".", result.source_dir);
+        assert_eq!(StringVector(vec![String::from("FirstClass.java"), String::from("SecondClass.java")]), result.get_source_files());
+        assert_eq!(None, result.get_packages());
+    }
+
+    #[test]
+    fn extract_sources_with_modules() {
+        let root = Path::new("./test-dirs/test-java-modules");
+        let mut result = JavaData::extract_sources(root).unwrap();
+        result.main_class = Some(String::from("com.example.app.App"));
+
+        let expected_modules = vec![
+            JavaModule {
+                name: String::from("com.example.app"),
+                root: String::from("src/com.example.app"),
+                requires: vec![String::from("com.example.greeter"), String::from("java.logging")],
+            },
+            JavaModule {
+                name: String::from("com.example.greeter"),
+                root: String::from("src/com.example.greeter"),
+                requires: Vec::new(),
+            },
+        ];
+
+        assert_eq!(expected_modules, result.modules);
+        assert_eq!(StringVector(vec![
+            String::from("out/com.example.app/com/example/app/App.class"),
+            String::from("out/com.example.app/module-info.class"),
+            String::from("out/com.example.greeter/com/example/greeter/Greeter.class"),
+            String::from("out/com.example.greeter/module-info.class"),
+        ]), result.get_compiled_files());
+        assert_eq!(StringVector(vec![String::from("src")]), result.get_module_source_path());
+        assert_eq!(Some(String::from("com.example.app")), result.get_main_module());
+        assert!(result.get_external_requires().is_empty());
+        assert_eq!(vec![
+            PackagedSources {
+                jar_name: String::from("com.example.app-sources.jar"),
+                root: String::from("src/com.example.app"),
+                files: StringVector(vec![String::from("com/example/app/App.java"), String::from("module-info.java")]),
+            },
+            PackagedSources {
+                jar_name: String::from("com.example.greeter-sources.jar"),
+                root: String::from("src/com.example.greeter"),
+                files: StringVector(vec![String::from("com/example/greeter/Greeter.java"), String::from("module-info.java")]),
+            },
+        ], result.get_packaged_sources());
+        assert_eq!("App", result.get_artifact_name());
+
+        result.set_resource_dir(root, "src/com.example.app/com/").unwrap();
+        assert_eq!(StringVector(vec![String::from("out/com.example.app/example/app/App.java")]),
+            result.get_copied_resource_files());
+    }
+
+    #[test]
+    fn module_source_path_for_single_module() {
+        let mut java_data = JavaData::extract_sources(Path::new("./test-dirs/test-java-makefile-creation")).unwrap();
+        java_data.modules.push(JavaModule {
+            name: String::from("com.example"),
+            root: String::from("src"),
+            requires: vec![String::from("org.slf4j"), String::from("java.sql")],
+        });
+
+        assert_eq!(StringVector(vec![String::from("com.example=src")]), java_data.get_module_source_path());
+        assert_eq!(vec![String::from("org.slf4j")], java_data.get_external_requires());
+    }
+
+    #[test]
+    fn reading_module_declarations() {
+        let code = "import java.lang.annotation.*;\n\n@Deprecated\nopen module com.example.app {\n    requires transitive com.example.api;\n    requires static lombok;\n    requires java.sql;\n    exports com.example.app;\n}\n";
+
+        let expected = (String::from("com.example.app"),
+            vec![String::from("com.example.api"), String::from("lombok"), String::from("java.sql")]);
+
+        assert_eq!(Some(expected), read_module_declaration(code));
+        assert_eq!(None, read_module_declaration("public class App {}"));
+    }
+
+    #[test]
+    fn reading_package_declarations() {
+        let code = "/* package wrong;\n */\n// package wrong;\npackage  com.example ;\n\npublic class App {}\n";
+
+        assert_eq!("com.example", read_package(&strip_comments(code)));
+        assert_eq!("", read_package("public class App { String packageName; }"));
+    }
+
+    #[test]
+    fn extract_kotlin_sources() {
+        let root = Path::new("./test-dirs/test-kotlin");
+        let mut result = JavaData::extract_sources(root).unwrap();
+        result.extract_kotlin_sources(root).unwrap();
+
+        assert_eq!(StringVector(vec![String::from("src/com/example/Greeter.java")]), result.get_source_files());
+        assert_eq!(StringVector(vec![String::from("src/com/example/App.kt"), String::from("src/com/example/util/Strings.kt")]),
+            result.get_kotlin_source_files());
+        assert_eq!("StringsKt", result.kotlin_sources[1].class_name);
+        assert_eq!(vec![String::from("com.example.AppKt")], result.get_main_classes());
+    }
+
+    #[test]
+    fn extract_scala_sources() {
+        let root = Path::new("./test-dirs/test-scala");
+        let mut result = JavaData::extract_sources(root).unwrap();
+        result.extract_scala_sources(root).unwrap();
+
+        assert_eq!(StringVector(vec![String::from("src/com/example/Main.scala"), String::from("src/com/example/Shapes.scala")]),
+            result.get_scala_source_files());
+        assert_eq!(vec![String::from("com.example.Launcher")], result.get_main_classes());
+    }
+
+    #[test]
+    fn reading_scala_main_objects() {
+        assert_eq!(Some(String::from("Hello")), read_scala_main_object("object Hello extends App {\n  println(1)\n}"));
+        assert_eq!(Some(String::from("Tool")), read_scala_main_object("object Tool extends Base with App"));
+        assert_eq!(Some(String::from("Runner")),
+            read_scala_main_object("object Runner {\n  def main(args: Array[String]): Unit = {}\n}"));
+        assert_eq!(Some(String::from("hello")), read_scala_main_object("@main def hello(): Unit = println(1)"));
+        assert_eq!(None, read_scala_main_object("class Main {\n  def mainly(): Unit = {}\n}\nobject Apps extends Application"));
+        assert_eq!(Some(String::from("Runner")),
+            read_scala_main_object("object Runner:\n  def main(args: Array[String]): Unit = ()"));
+    }
+
+    #[test]
+    fn ignoring_scala_main_methods_outside_of_objects() {
+        assert_eq!(None, read_scala_main_object("object A {}\nclass B {\n  def main(args: Array[String]) = {}\n}"));
+        assert_eq!(None, read_scala_main_object("object A {\n  class B {\n    def main(args: Array[String]) = {}\n  }\n}"));
+        assert_eq!(None, read_scala_main_object("object A:\n  val x = 1\nclass B:\n  def main(args: Array[String]) = ()"));
+        assert_eq!(Some(String::from("B")),
+            read_scala_main_object("object A {}\nobject B {\n  def main(args: Array[String]) = {}\n}"));
+    }
+
+    #[test]
+    fn detecting_kotlin_main_functions() {
+        assert!(has_kotlin_main_function("fun main() {\n    println(\"Hi\")\n}"));
+        assert!(has_kotlin_main_function("package a\n\nsuspend fun main (args: Array<String>) = run()"));
+        assert!(!has_kotlin_main_function("object App {\n    fun main(args: Array<String>) {}\n}"));
+        assert!(!has_kotlin_main_function("fun mainly() {}\nval main = 1"));
+    }
+
+    #[test]
+    fn detecting_main_methods() {
+        assert!(has_main_method("class A { public static void main(String[] args) {} }"));
+        assert!(has_main_method("class A {\n    static public void main (final String... args) {}\n}"));
+        assert!(has_main_method("class A { public static void main(String args[]) {} }"));
+        assert!(!has_main_method("class A { public void main(String[] args) {} }"));
+        assert!(!has_main_method("class A { static void main(String[] args) {} }"));
+        assert!(!has_main_method("class A { public static void main(int x) {} }"));
+    }
+
+    #[test]
+    fn reading_loaded_libraries() {
+        let code = "class A {\n    static {\n        System.loadLibrary ( \"native\" );\n        System.loadLibrary(NAME);\n    }\n}\n";
+
+        assert_eq!(vec![String::from("native")], read_loaded_libraries(code));
+        assert!(read_loaded_libraries("class A { void loadLibrary() {} }").is_empty());
+    }
+
+    #[test]
+    fn detecting_test_annotations() {
+        assert!(has_test_annotations("class ATest { @Test void works() {} }"));
+        assert!(has_test_annotations("class ATest { @ParameterizedTest\n void works(int x) {} }"));
+        assert!(!has_test_annotations("@TestInstance(Lifecycle.PER_CLASS) class A {}"));
+        assert!(!has_test_annotations("class A { void test() {} }"));
+    }
+}
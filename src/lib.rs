@@ -4,18 +4,30 @@
 //!
 //! Run `makewiz java` to generate a Makefile for your Java project.
 //!
+//! Run `makewiz jni` to generate a Makefile for a Java project with native C/C++ code.
+//!
+//! Run `makewiz kotlin` to generate a Makefile for your Kotlin project.
+//!
+//! Run `makewiz scala` to generate a Makefile for your Scala project.
+//!
 //! For a more detailed usage guide and installation guide, visit [MakeWiz's GitHub page](https://github.com/kallazz/MakeWiz).
 
 pub mod cli;
+pub mod compiler_preset;
 pub mod build_data;
+pub mod java_data;
+pub mod diff;
+pub mod output;
+pub mod provenance;
 pub mod user_config;
 
 use std::fmt;
 
-use crate::build_data::BuildData;
+use crate::build_data::{BuildData, Language, ObjcRuntime};
+use crate::java_data::JavaData;
 
 /// Represents a vector of strings.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Default)]
 pub struct StringVector(Vec<String>);
 
 impl StringVector {
@@ -32,6 +44,21 @@ impl fmt::Display for StringVector {
     }
 }
 
+/// The parts of a generated Makefile, so the sections of different languages can be combined.
+struct MakefileSections {
+    /// Variable definitions.
+    variables: String,
+
+    /// Prerequisites of the default target.
+    default_prerequisites: String,
+
+    /// Rules following the default target.
+    rules: String,
+
+    /// Files and directories removed by the clean rule.
+    clean_files: String,
+}
+
 /// Generates a Makefile for a C/C++ project based on the provided `BuildData`.
 ///
 /// # Arguments
@@ -42,72 +69,1097 @@ impl fmt::Display for StringVector {
 ///
 /// A `String` containing the generated Makefile.
 pub fn generate_makefile(file_names: &BuildData) -> String {
-    let makefile = format!("\
+    let sections = generate_cpp_sections(file_names, "SOURCE");
+
+    format!("{}
+# Default target
+all: {}
+{}
+# Clean rule
+clean:
+\trm -f {}\n",
+sections.variables, sections.default_prerequisites, sections.rules, sections.clean_files)
+}
+
+fn generate_cpp_sections(file_names: &BuildData, source_variable: &str) -> MakefileSections {
+    let variables = format!("\
 # Compiler and flags
-CC = {}
-FLAGS = -g -c -Wall
+{}{}FLAGS = -g -c -Wall{}{}
 LFLAGS = {}
-
+{}
 # Source files and object files
 OBJS = {}
-SOURCE = {}
+{} = {}
 HEADER = {}
-OUT = {}
+{}OUT = {}
 
 # Libraries
 LDLIBS = {}
+",
+generate_compiler_variables(file_names), generate_driver_variables(file_names, "OBJS"), get_library_flags(file_names), get_driver_compile_flags(file_names),
+get_link_flags(file_names), generate_tool_variables(file_names, ""), file_names.get_cpp_compiled_files(), source_variable,
+file_names.get_cpp_source_files(), get_header_files(file_names), generate_output_variables(file_names), file_names.executable,
+get_ldlibs(file_names));
 
-# Default target
-all: $(OUT)
-
+    let mut rules = format!("
 # Linking rules
 $(OUT): $(OBJS)
-\t$(CC) -g $(OBJS) -o $(OUT) $(LFLAGS) $(LDLIBS)
+\t{} -g $(OBJS) -o $(OUT) $(LFLAGS) $(LDLIBS)
 
 # Compilation rules
-%.o: %.cpp $(HEADER)
-\t$(CC) $(FLAGS) -o $@ $<
+", get_linker(file_names));
 
-# Clean rule
+    // One pattern rule for every extension of the sources, e.g. .c, .cpp or .cc
+    let mut extensions = get_rule_extensions(file_names);
+    if extensions.is_empty() {
+        extensions.push("cpp");
+    }
+    for extension in extensions {
+        rules.push_str(&generate_pattern_rule(file_names, extension, "$(HEADER)", ""));
+    }
+    rules.push_str(&generate_dependency_rules(file_names));
+
+    MakefileSections {
+        variables,
+        default_prerequisites: String::from("$(OUT)"),
+        rules,
+        clean_files: format!("$(OBJS) $(OUT){}", get_output_clean_files(file_names)),
+    }
+}
+
+/// Generates the variables of the tools and flags compiling the sources that aren't C/C++, i.e. the assembler
+/// for assembly, the Fortran compiler for Fortran, Flex and Bison for lexers and parsers, protoc for protobuf
+/// definitions, and the Objective-C flags for Objective-C/C++.
+///
+/// # Arguments
+///
+/// * `file_names` - A `BuildData` struct containing the file names.
+/// * `extra_flags` - Flags appended to the default Fortran and Objective-C flags, e.g. ` -fPIC`.
+fn generate_tool_variables(file_names: &BuildData, extra_flags: &str) -> String {
+    let mut variables = String::new();
+
+    if file_names.has_sources(Language::Assembly) {
+        variables.push_str("AS = as\nASFLAGS = -g\n");
+    }
+    if file_names.has_sources(Language::Fortran) {
+        variables.push_str(&format!("FC = gfortran\nFFLAGS = -g -Wall{}\n", extra_flags));
+    }
+    if file_names.has_sources(Language::Lex) {
+        variables.push_str("LEX = flex\n");
+    }
+    if file_names.has_sources(Language::Yacc) {
+        variables.push_str("YACC = bison\nYFLAGS = -d\n");
+    }
+    if file_names.has_sources(Language::Proto) {
+        variables.push_str("PROTOC = protoc\n\
+            PROTOBUF_CFLAGS := $(shell pkg-config --cflags protobuf 2>/dev/null)\n\
+            PROTOBUF_LIBS := $(shell pkg-config --libs protobuf 2>/dev/null || echo -lprotobuf)\n");
+    }
+    if has_objc_sources(file_names) {
+        let runtime_flags = match file_names.objc_runtime {
+            ObjcRuntime::Objc => "",
+            ObjcRuntime::Gnustep => " $(shell gnustep-config --objc-flags)",
+        };
+        variables.push_str(&format!("OBJCFLAGS = -g -c -Wall{}{}{}\n", extra_flags, runtime_flags, get_driver_compile_flags(file_names)));
+    }
+
+    variables
+}
+
+/// Generates the compiler variables, `CC` and also `CXX` if C and C++ sources are compiled by different compilers.
+fn generate_compiler_variables(file_names: &BuildData) -> String {
+    match get_cpp_compiler(file_names) {
+        Some(cpp_compiler) => format!("CC = {}\nCXX = {}\n", get_compiler(file_names), cpp_compiler),
+        None => format!("CC = {}\n", get_compiler(file_names)),
+    }
+}
+
+/// Returns the command of the C/C++ compiler, from the compiler driver preset if there is one.
+///
+/// The preset's C++ driver is used if there are C++ sources, including the ones generated by protoc.
+/// C sources are compiled by the C compiler belonging to a C++ compiler, e.g. `gcc` for `g++`,
+/// so they aren't compiled as C++. The C++ sources are then compiled by [`get_cpp_compiler`].
+fn get_compiler(file_names: &BuildData) -> String {
+    if let (true, Some(c_compiler)) = (has_c_sources(file_names), get_c_compiler(file_names)) {
+        return c_compiler;
+    }
+
+    match &file_names.driver {
+        Some(driver) => driver.command(has_cpp_sources(file_names)),
+        None => file_names.compiler.clone(),
+    }
+}
+
+/// Returns the command of the C++ compiler if there are both C and C++ sources, compiled by different compilers.
+fn get_cpp_compiler(file_names: &BuildData) -> Option<String> {
+    if !has_cpp_sources(file_names) || !has_c_sources(file_names) {
+        return None;
+    }
+    get_c_compiler(file_names)?;
+
+    match &file_names.driver {
+        Some(driver) => Some(driver.command(true)),
+        None => Some(file_names.compiler.clone()),
+    }
+}
+
+/// Returns the command of the C compiler, if it's different from the C++ compiler.
+fn get_c_compiler(file_names: &BuildData) -> Option<String> {
+    match &file_names.driver {
+        Some(driver) => Some(driver.command(false)).filter(|command| *command != driver.command(true)),
+        None => compiler_preset::get_c_compiler(&file_names.compiler),
+    }
+}
+
+/// Checks if there are C++ sources, including the ones generated by protoc.
+fn has_cpp_sources(file_names: &BuildData) -> bool {
+    [Language::Cpp, Language::ObjCpp, Language::Proto].into_iter()
+        .any(|language| file_names.has_sources(language))
+}
+
+/// Checks if there are C sources, including preprocessed assembly and the sources generated by Flex and Bison.
+fn has_c_sources(file_names: &BuildData) -> bool {
+    [Language::C, Language::PreprocessedAssembly, Language::Lex, Language::Yacc].into_iter()
+        .any(|language| file_names.has_sources(language))
+}
+
+/// Generates the variables of the compiler driver preset: the flags choosing the target, enabling
+/// the sanitizers and generating the dependency files, and the dependency files of the objects.
+///
+/// # Arguments
+///
+/// * `file_names` - A `BuildData` struct containing the file names and the compiler driver.
+/// * `objects_variable` - The variable listing the objects, e.g. `OBJS`.
+fn generate_driver_variables(file_names: &BuildData, objects_variable: &str) -> String {
+    let mut variables = String::new();
+
+    if let Some(driver) = &file_names.driver {
+        if let Some(target_flags) = driver.target_flags() {
+            variables.push_str(&format!("TARGET_FLAGS = {}\n", target_flags));
+        }
+        if let Some(sanitize_flags) = driver.sanitize_flags() {
+            variables.push_str(&format!("SANITIZE_FLAGS = {}\n", sanitize_flags));
+        }
+        variables.push_str(&format!("DEPFLAGS = {}\n", driver.preset.dependency_flags()));
+        variables.push_str(&format!("DEPS = $({}:.o=.d)\n", objects_variable));
+    }
+
+    variables
+}
+
+/// Returns the variables from [`generate_driver_variables`] passed when compiling, each preceded by a space.
+fn get_driver_compile_flags(file_names: &BuildData) -> String {
+    match &file_names.driver {
+        Some(_) => format!("{} $(DEPFLAGS)", get_driver_link_flags(file_names)),
+        None => String::new(),
+    }
+}
+
+/// Returns the variables from [`generate_driver_variables`] passed when linking, each preceded by a space.
+fn get_driver_link_flags(file_names: &BuildData) -> String {
+    let mut flags = String::new();
+
+    if let Some(driver) = &file_names.driver {
+        if driver.target_flags().is_some() {
+            flags.push_str(" $(TARGET_FLAGS)");
+        }
+        if driver.sanitize_flags().is_some() {
+            flags.push_str(" $(SANITIZE_FLAGS)");
+        }
+    }
+
+    flags
+}
+
+/// Returns the linker flags, preceded by the target and sanitizer flags of the compiler driver.
+fn get_link_flags(file_names: &BuildData) -> String {
+    format!("{} {}", get_driver_link_flags(file_names), file_names.lflags).trim().to_string()
+}
+
+/// Returns the flag generating position independent code for the shared library, preceded by a space.
+fn get_pic_flag(file_names: &BuildData) -> String {
+    match &file_names.driver {
+        Some(driver) => driver.preset.pic_flag().map(|flag| format!(" {}", flag)).unwrap_or_default(),
+        None => String::from(" -fPIC"),
+    }
+}
+
+/// Returns the compiler flags of the libraries used by the sources, i.e. the protobuf include paths.
+fn get_library_flags(file_names: &BuildData) -> &'static str {
+    match file_names.has_sources(Language::Proto) {
+        true => " $(PROTOBUF_CFLAGS)",
+        false => "",
+    }
+}
+
+/// Checks if there are Objective-C or Objective-C++ sources.
+fn has_objc_sources(file_names: &BuildData) -> bool {
+    file_names.has_sources(Language::ObjC) || file_names.has_sources(Language::ObjCpp)
+}
+
+/// Returns the header files, including the headers generated by Bison, so the objects are compiled after them.
+fn get_header_files(file_names: &BuildData) -> StringVector {
+    let mut header_files = StringVector(file_names.header_files.0.clone());
+    header_files.0.extend(file_names.get_generated_headers().0);
+
+    header_files
+}
+
+/// Returns the extensions of the sources compiled by the pattern rules, including the extensions
+/// of the sources generated by Flex, Bison and protoc.
+fn get_rule_extensions(file_names: &BuildData) -> Vec<&str> {
+    let mut extensions = file_names.get_cpp_source_extensions();
+
+    extensions.extend(file_names.get_generated_source_extensions());
+    extensions.sort();
+    extensions.dedup();
+
+    extensions
+}
+
+/// Generates the variables listing the files written during the build, besides the objects:
+/// the module files of the Fortran compiler and the sources generated by Flex, Bison and protoc.
+fn generate_output_variables(file_names: &BuildData) -> String {
+    let mut variables = String::new();
+
+    let module_files = file_names.get_fortran_module_files();
+    if !module_files.0.is_empty() {
+        variables.push_str(&format!("MODS = {}\n", module_files));
+    }
+
+    let generated_files = file_names.get_generated_files();
+    if !generated_files.0.is_empty() {
+        variables.push_str(&format!("GENERATED = {}\n", generated_files));
+    }
+
+    variables
+}
+
+/// Returns the variables from [`generate_output_variables`] to clean, each preceded by a space.
+fn get_output_clean_files(file_names: &BuildData) -> String {
+    let mut clean_files = String::new();
+
+    if !file_names.get_fortran_module_files().0.is_empty() {
+        clean_files.push_str(" $(MODS)");
+    }
+    if !file_names.get_generated_files().0.is_empty() {
+        clean_files.push_str(" $(GENERATED)");
+    }
+    if file_names.driver.is_some() {
+        clean_files.push_str(" $(DEPS)");
+    }
+
+    clean_files
+}
+
+/// Generates the rules ordering the build, besides the pattern rules.
+///
+/// Fortran objects are compiled after the objects providing the modules they use, since the module
+/// files have to exist before a source using them is compiled. The sources generated by Flex, Bison
+/// and protoc are kept, as make would otherwise delete them as intermediate files. With a compiler driver
+/// preset, the dependency files written by the compiler are included, so objects are rebuilt when their headers change.
+fn generate_dependency_rules(file_names: &BuildData) -> String {
+    let mut rules = String::new();
+
+    let dependencies = file_names.get_fortran_dependencies();
+    if !dependencies.is_empty() {
+        rules.push_str("\n# Fortran module dependencies\n");
+        for (object, required_objects) in dependencies {
+            rules.push_str(&format!("{}: {}\n", object, required_objects));
+        }
+    }
+
+    if !file_names.get_generated_files().0.is_empty() {
+        rules.push_str("\n# Keep the generated sources\n.SECONDARY: $(GENERATED)\n");
+    }
+
+    if file_names.driver.is_some() {
+        rules.push_str("\n# Header dependencies written by the compiler\n-include $(DEPS)\n");
+    }
+
+    rules
+}
+
+/// Returns the driver linking the objects.
+///
+/// Fortran objects are linked by the Fortran compiler, which links the Fortran runtime, unless
+/// there are C++ objects too. Then the C++ compiler links them, see [`get_ldlibs`].
+fn get_linker(file_names: &BuildData) -> &'static str {
+    if file_names.has_sources(Language::Fortran) && !file_names.has_sources(Language::Cpp) {
+        return "$(FC)";
+    }
+
+    match get_cpp_compiler(file_names) {
+        Some(_) => "$(CXX)",
+        None => "$(CC)",
+    }
+}
+
+/// Returns the libraries to link, with the Fortran runtime if Fortran objects are linked by the C/C++ compiler,
+/// the Objective-C runtime if there are Objective-C objects, and the protobuf library if there are protobuf definitions.
+fn get_ldlibs(file_names: &BuildData) -> String {
+    let mut ldlibs = file_names.ldlibs.clone();
+
+    if get_linker(file_names) != "$(FC)" && file_names.has_sources(Language::Fortran) {
+        ldlibs.push_str(" -lgfortran");
+    }
+    if has_objc_sources(file_names) {
+        ldlibs.push_str(match file_names.objc_runtime {
+            ObjcRuntime::Objc => " -lobjc",
+            ObjcRuntime::Gnustep => " $(shell gnustep-config --base-libs)",
+        });
+    }
+    if file_names.has_sources(Language::Proto) {
+        ldlibs.push_str(" $(PROTOBUF_LIBS)");
+    }
+
+    ldlibs.trim_start().to_string()
+}
+
+/// Generates the pattern rule compiling the sources with the given extension into object files.
+///
+/// Assembly (.s) is compiled by the assembler and Fortran by the Fortran compiler. Everything else,
+/// including preprocessed assembly (.S), is compiled by the C/C++ compiler driver, with `OBJCFLAGS`
+/// instead of `FLAGS` for Objective-C/C++. If there's a separate C++ compiler, it compiles the C++ and
+/// Objective-C++ sources. Lexers (.l), parsers (.y) and protobuf definitions (.proto) get rules
+/// generating their C/C++ sources, which are compiled by the rules for .c and .cc files.
+fn generate_pattern_rule(file_names: &BuildData, extension: &str, header: &str, include: &str) -> String {
+    let language = file_names.get_extension_language(extension);
+    let compiler = match (get_cpp_compiler(file_names), language) {
+        (Some(_), Some(Language::C | Language::PreprocessedAssembly | Language::ObjC)) => "$(CC)",
+        (Some(_), _) => "$(CXX)",
+        (None, _) => "$(CC)",
+    };
+
+    match language {
+        Some(Language::Assembly) => format!("%.o: %.{}\n\t$(AS) $(ASFLAGS) -o $@ $<\n", extension),
+        Some(Language::Fortran) => format!("%.o: %.{}\n\t$(FC) $(FFLAGS) -c -o $@ $<\n", extension),
+        Some(Language::Lex) => format!("%.yy.c: %.{}\n\t$(LEX) -o $@ $<\n", extension),
+        Some(Language::Yacc) => format!("%.tab.c %.tab.h: %.{}\n\t$(YACC) $(YFLAGS) -o $*.tab.c $<\n", extension),
+        Some(Language::Proto) => format!("%.pb.cc %.pb.h: %.{}\n\t$(PROTOC) --cpp_out=. $<\n", extension),
+        Some(Language::ObjC | Language::ObjCpp) =>
+            format!("%.o: %.{} {}\n\t{} $(OBJCFLAGS){} -o $@ $<\n", extension, header, compiler, include),
+        _ => format!("%.o: %.{} {}\n\t{} $(FLAGS){} -o $@ $<\n", extension, header, compiler, include),
+    }
+}
+
+/// Generates a single Makefile for a project mixing C/C++ and Java, based on the provided `BuildData` and `JavaData`.
+///
+/// Every language found in the project gets its own section with variables and rules, as in
+/// [`generate_makefile`] and [`generate_java_makefile`]. The `all` and `clean` targets cover all of them.
+/// The C/C++ sources are listed in `CC_SOURCE`, since `SOURCE` holds the Java sources.
+///
+/// # Arguments
+///
+/// * `file_names` - A `BuildData` struct containing the C/C++ file names and compiler options.
+/// * `java_data` - A `JavaData` struct containing the Java source files and directories.
+///
+/// # Returns
+///
+/// A `String` containing the generated Makefile.
+pub fn generate_mixed_makefile(file_names: &BuildData, java_data: &JavaData) -> String {
+    let has_java = !java_data.sources.is_empty();
+    let has_cpp = !file_names.get_cpp_source_files().0.is_empty() || !has_java;
+
+    let mut sections = Vec::new();
+    if has_cpp {
+        sections.push(("C/C++", generate_cpp_sections(file_names, "CC_SOURCE")));
+    }
+    if has_java {
+        sections.push(("Java", generate_java_sections(java_data, None)));
+    }
+
+    let variables: Vec<String> = sections.iter()
+        .map(|(language, section)| format!("# ---------- {} ----------\n\n{}", language, section.variables))
+        .collect();
+    let sections: Vec<MakefileSections> = sections.into_iter().map(|(_, section)| section).collect();
+    let default_prerequisites: Vec<&str> = sections.iter().map(|section| section.default_prerequisites.as_str()).collect();
+    let rules: String = sections.iter().map(|section| section.rules.as_str()).collect();
+    let clean_files: Vec<&str> = sections.iter().map(|section| section.clean_files.as_str()).collect();
+
+    format!("{}
+# Default target, building every language
+all: {}
+{}
+# Clean rule for every language
 clean:
-\trm -f $(OBJS) $(OUT)\n",
-file_names.compiler, file_names.lflags,
-file_names.get_cpp_compiled_files(), file_names.get_cpp_source_files(),
-file_names.header_files, file_names.executable, file_names.ldlibs);
+\trm -rf {}\n",
+variables.join("\n"), default_prerequisites.join(" "), rules, clean_files.join(" "))
+}
+
+/// Generates a Makefile for a Java project based on the provided `JavaData`.
+///
+/// All sources are compiled with a single `javac` invocation, driven by a generated sources list file.
+/// If a main class is set, `jar` and `run` targets are added as well. Classpath entries are passed
+/// to `javac` and `java`, and listed in the `Class-Path` of the jar manifest. If there are test classes,
+/// they are compiled separately against the main classes and run by the `test` target.
+///
+/// Modular projects are compiled with `--module-source-path` and `--module` instead, with
+/// the dependencies on the module path. The main class is then run from its module.
+/// Resources are copied into the output directory, so they're on the classpath and in the jar.
+/// Optionally, `doc` and `sources-jar` targets generate the javadoc and package the source files.
+///
+/// # Arguments
+///
+/// * `java_data` - A `JavaData` struct containing the Java source files and directories.
+///
+/// # Returns
+///
+/// A `String` containing the generated Makefile.
+pub fn generate_java_makefile(java_data: &JavaData) -> String {
+    assemble_java_makefile(generate_java_sections(java_data, None))
+}
 
-    makefile
+/// Generates a Makefile for a Java project using JNI, based on the provided `JavaData` and `BuildData`.
+///
+/// The Java sources are compiled like in [`generate_java_makefile`], with `javac -h` generating
+/// the headers for the native methods. The C/C++ sources from `BuildData` are compiled with
+/// the JDK include paths and linked into a shared library, which is loaded by the `run` and `test` targets.
+///
+/// # Arguments
+///
+/// * `java_data` - A `JavaData` struct containing the Java source files and directories.
+/// * `native_data` - A `BuildData` struct containing the C/C++ file names, compiler and linker options.
+/// * `library` - The name of the shared library, as passed to `System.loadLibrary`.
+///
+/// # Returns
+///
+/// A `String` containing the generated Makefile.
+pub fn generate_jni_makefile(java_data: &JavaData, native_data: &BuildData, library: &str) -> String {
+    assemble_java_makefile(generate_java_sections(java_data, Some((native_data, library))))
 }
 
-/// Generates a Makefile for a Java project based on the provided `BuildData`.
+/// Generates a Makefile for a Kotlin project, optionally with Java sources, based on the provided `JavaData`.
+///
+/// The Kotlin sources are compiled first by `kotlinc`, which reads the Java sources to resolve references
+/// to Java classes. The Java sources are then compiled by `javac` against the Kotlin classes. The names of
+/// the classes compiled from Kotlin don't follow the file names, so a stamp file records when they were compiled.
+/// If a main class is set, `jar` packages the compiled classes with the unpacked Kotlin runtime into a runnable jar,
+/// and `run` runs it.
 ///
 /// # Arguments
 ///
-/// * `file_names` - A `BuildData` struct containing relevant file names.
+/// * `java_data` - A `JavaData` struct containing the Kotlin and Java source files and directories.
 ///
 /// # Returns
 ///
 /// A `String` containing the generated Makefile.
-pub fn generate_java_makefile(file_names: &BuildData) -> String {
-    let makefile = format!("\
-# Compiler and flags
-JC = javac
-JFLAGS = -g
+pub fn generate_kotlin_makefile(java_data: &JavaData) -> String {
+    let has_java = !java_data.sources.is_empty();
+    let has_main = java_data.main_class.is_some();
 
-# Source files and compiled classes
+    let mut variables = String::from("# Compilers and flags\nKC = kotlinc\nKFLAGS =\n");
+    if has_java {
+        let (flags, flag_variables) = generate_javac_flags(java_data);
+        variables.push_str(&format!("JC = javac\nJFLAGS = {}\n{}", flags, flag_variables));
+    }
+    if has_main {
+        variables.push_str("JAR = jar\nJAVA = java\n");
+    }
+
+    variables.push_str(&format!("
+# Directories
+OUT_DIR = {}
+
+# Source files, Kotlin is compiled before Java
+KOTLIN_SOURCE = {}
+KOTLIN_STAMP = kotlin.stamp
+",
+java_data.output_dir, java_data.get_kotlin_source_files()));
+
+    if has_java {
+        variables.push_str(&format!("SOURCE = {}\nCLASSES_STAMP = classes.stamp\nSOURCES_LIST = sources.txt\n",
+            java_data.get_source_files()));
+    }
+
+    // javac needs the Kotlin standard library for the Kotlin classes using it, and the runnable jar includes it
+    if has_java || has_main {
+        variables.push_str("
+# Kotlin standard library, next to kotlinc
+KOTLIN_HOME ?= $(shell dirname $$(dirname $$(readlink -f $$(which kotlinc))))
+KOTLIN_STDLIB = $(KOTLIN_HOME)/lib/kotlin-stdlib.jar
+");
+    }
+
+    let (compile_path, class_path) = match java_data.classpath.is_empty() {
+        true => (String::new(), String::new()),
+        false => {
+            variables.push_str(&format!("
+# Classpath with dependency JARs and directories
+CLASS_PATH = {}
+",
+java_data.classpath.join(":")));
+
+            (String::from(" -cp $(CLASS_PATH)"), String::from(":$(CLASS_PATH)"))
+        }
+    };
+
+    let mut clean_files = String::from("$(OUT_DIR) $(KOTLIN_STAMP)");
+    if has_java {
+        clean_files.push_str(" $(CLASSES_STAMP) $(SOURCES_LIST)");
+    }
+
+    if let Some(main_class) = &java_data.main_class {
+        let artifact_name = java_data.get_artifact_name();
+
+        variables.push_str(&format!("
+# Main class and runnable jar, with the Kotlin runtime unpacked into RUNTIME_DIR
+MAIN_CLASS = {}
+JAR_FILE = {}.jar
+RUNTIME_DIR = out-runtime
+",
+main_class, artifact_name.strip_suffix("Kt").unwrap_or(&artifact_name)));
+        clean_files.push_str(" $(JAR_FILE) $(RUNTIME_DIR)");
+    }
+
+    let (java_sources, java_classes) = match has_java {
+        true => (" $(SOURCE)", " $(CLASSES_STAMP)"),
+        false => ("", ""),
+    };
+
+    let mut rules = format!("
+# Kotlin is compiled first, kotlinc reads the Java sources to resolve the Java classes
+$(KOTLIN_STAMP): $(KOTLIN_SOURCE){0}
+\t@mkdir -p $(OUT_DIR)
+\t$(KC) $(KFLAGS) -d $(OUT_DIR){1} $(KOTLIN_SOURCE){0}
+\t@touch $(KOTLIN_STAMP)
+",
+java_sources, compile_path);
+
+    if has_java {
+        rules.push_str(SOURCES_LIST_RULE);
+        rules.push_str(&format!("
+# Java is compiled against the Kotlin classes
+$(CLASSES_STAMP): $(SOURCES_LIST) $(KOTLIN_STAMP)
+\t$(JC) $(JFLAGS) -d $(OUT_DIR) -cp $(OUT_DIR):$(KOTLIN_STDLIB){} @$(SOURCES_LIST)
+\t@touch $(CLASSES_STAMP)
+",
+class_path));
+    }
+
+    if has_main {
+        // The manifest of the runtime is replaced by the one of the jar, and the runtime's module descriptor is dropped
+        rules.push_str(&format!("
+# Runnable jar with the compiled classes and the Kotlin runtime
+jar: $(JAR_FILE)
+
+$(JAR_FILE): $(KOTLIN_STAMP){0}
+\t@rm -rf $(RUNTIME_DIR) && mkdir -p $(RUNTIME_DIR)
+\tcd $(RUNTIME_DIR) && $(JAR) xf $(abspath $(KOTLIN_STDLIB))
+\t@rm -rf $(RUNTIME_DIR)/META-INF/MANIFEST.MF $(RUNTIME_DIR)/META-INF/versions
+\t$(JAR) --create --file $(JAR_FILE) --main-class $(MAIN_CLASS) -C $(OUT_DIR) . -C $(RUNTIME_DIR) .
+
+# Run the main class
+run: $(JAR_FILE)
+\t$(JAVA) -cp $(JAR_FILE){1} $(MAIN_CLASS)
+",
+java_classes, class_path));
+    }
+
+    assemble_java_makefile(MakefileSections {
+        variables,
+        default_prerequisites: format!("$(KOTLIN_STAMP){}", java_classes),
+        rules,
+        clean_files,
+    })
+}
+
+/// Generates a Makefile for a Scala project compiled with `scalac`, based on the provided `JavaData`.
+///
+/// All sources are compiled with a single `scalac` invocation into the output directory. The names of
+/// the classes compiled from Scala don't follow the file names, so a stamp file records when they were compiled.
+/// Classpath entries are passed to `scalac` and `scala`. If a main object is set, the `run` target runs it.
+///
+/// # Arguments
+///
+/// * `java_data` - A `JavaData` struct containing the Scala source files and directories.
+///
+/// # Returns
+///
+/// A `String` containing the generated Makefile.
+pub fn generate_scala_makefile(java_data: &JavaData) -> String {
+    let mut variables = String::from("# Compiler and flags\nSC = scalac\nSCFLAGS =\n");
+    if java_data.main_class.is_some() {
+        variables.push_str("SCALA = scala\n");
+    }
+
+    variables.push_str(&format!("
+# Directories
+OUT_DIR = {}
+
+# Source files
 SOURCE = {}
-CLASSES = {}
+SCALA_STAMP = scala.stamp
+",
+java_data.output_dir, java_data.get_scala_source_files()));
 
-# Default target
-default: $(CLASSES)
+    let (compile_path, class_path) = match java_data.classpath.is_empty() {
+        true => (String::new(), String::new()),
+        false => {
+            variables.push_str(&format!("
+# Classpath with dependency JARs and directories
+CLASS_PATH = {}
+",
+java_data.classpath.join(":")));
+
+            (String::from(" -classpath $(CLASS_PATH)"), String::from(":$(CLASS_PATH)"))
+        }
+    };
 
+    let mut rules = format!("
 # Compilation rule
-%.class: %.java
-\t$(JC) $(JFLAGS) $<
+$(SCALA_STAMP): $(SOURCE)
+\t@mkdir -p $(OUT_DIR)
+\t$(SC) $(SCFLAGS) -d $(OUT_DIR){} $(SOURCE)
+\t@touch $(SCALA_STAMP)
+",
+compile_path);
+
+    if let Some(main_class) = &java_data.main_class {
+        variables.push_str(&format!("
+# Main object
+MAIN_CLASS = {}
+",
+main_class));
+
+        rules.push_str(&format!("
+# Run the main object
+run: $(SCALA_STAMP)
+\t$(SCALA) -classpath $(OUT_DIR){} $(MAIN_CLASS)
+",
+class_path));
+    }
+
+    assemble_java_makefile(MakefileSections {
+        variables,
+        default_prerequisites: String::from("$(SCALA_STAMP)"),
+        rules,
+        clean_files: String::from("$(OUT_DIR) $(SCALA_STAMP)"),
+    })
+}
 
+fn assemble_java_makefile(sections: MakefileSections) -> String {
+    format!("{}
+# Default target
+default: {}
+{}
 # Clean rule to remove generated .class files
 clean:
-\trm -f $(CLASSES)\n",
-file_names.get_java_source_files(), file_names.get_java_compiled_files());
+\trm -rf {}\n",
+sections.variables, sections.default_prerequisites, sections.rules, sections.clean_files)
+}
+
+/// Rule writing the Java sources into the file passed to javac, so its command line stays short.
+const SOURCES_LIST_RULE: &str = "
+# List of source files passed to javac
+$(SOURCES_LIST): $(SOURCE)
+\t@printf '%s\\n' $(SOURCE) > $(SOURCES_LIST)
+";
+
+/// Generates the value of `JFLAGS` and the variables with the javac options it references.
+fn generate_javac_flags(java_data: &JavaData) -> (String, String) {
+    // The javac options are kept in separate variables, and referenced from JFLAGS
+    let mut flags = String::from("-g");
+    let mut flag_variables = String::new();
+    for (name, value) in &java_data.get_javac_flags() {
+        flags.push_str(&format!(" $({})", name));
+        flag_variables.push_str(&format!("{} = {}\n", name, value));
+    }
+
+    (flags, flag_variables)
+}
+
+fn generate_java_sections(java_data: &JavaData, native: Option<(&BuildData, &str)>) -> MakefileSections {
+    let modular = java_data.is_modular();
+    let (flags, flag_variables) = generate_javac_flags(java_data);
+
+    // Modules are found by javac in the module source path, so no sources list is needed
+    let (source_dir, sources_list) = match modular {
+        true => (format!("MODULE_SOURCE_PATH = {}", java_data.get_module_source_path()), ""),
+        false => (format!("SRC_DIR = {}", java_data.source_dir), "SOURCES_LIST = sources.txt\n"),
+    };
+
+    let mut variables = format!("\
+# Compiler and flags
+JC = javac
+JFLAGS = {}
+{}JAR = jar
+JAVA = java
+
+# Directories
+{}
+OUT_DIR = {}
+
+# Source files and compiled classes
+SOURCE = {}
+CLASSES_STAMP = classes.stamp
+{}",
+flags, flag_variables, source_dir, java_data.output_dir,
+java_data.get_source_files(), sources_list);
 
-    makefile
-}
\ No newline at end of file
+    let mut clean_files = String::from(match modular {
+        true => "$(OUT_DIR) $(CLASSES_STAMP)",
+        false => "$(OUT_DIR) $(CLASSES_STAMP) $(SOURCES_LIST)",
+    });
+
+    // javac generates the JNI headers for the native methods
+    let header_option = match native {
+        Some(_) => " -h $(JNI_HEADER_DIR)",
+        None => "",
+    };
+
+    // Directories with the compiled main classes, one per module in modular projects
+    let classes_dirs = match modular {
+        true => java_data.modules.iter()
+            .map(|module| format!("$(OUT_DIR)/{}", module.name))
+            .collect::<Vec<String>>()
+            .join(":"),
+        false => String::from("$(OUT_DIR)"),
+    };
+
+    if modular {
+        let module_names: Vec<&str> = java_data.modules.iter().map(|module| module.name.as_str()).collect();
+
+        variables.push_str(&format!("
+# Modules compiled by javac
+MODULES = {}
+",
+module_names.join(",")));
+    }
+
+    let resource_files = java_data.get_copied_resource_files();
+    let resources = match resource_files.0.is_empty() {
+        true => "",
+        false => " $(RESOURCE_FILES)",
+    };
+
+    if let (Some(resource_dir), false) = (&java_data.resource_dir, resources.is_empty()) {
+        variables.push_str(&format!("
+# Resources copied next to the compiled classes
+RESOURCE_DIR = {}
+RESOURCES = {}
+RESOURCE_FILES = {}
+",
+resource_dir, StringVector(java_data.resources.clone()), resource_files));
+    }
+
+    // Dependencies are on the classpath, or on the module path in modular projects
+    let dependencies = match modular {
+        true => "MODULE_PATH",
+        false => "CLASS_PATH",
+    };
+
+    // Dependencies option passed to javac, and the classpath used for running tests
+    let (compile_path, run_classpath) = match java_data.classpath.is_empty() {
+        true => (String::new(), classes_dirs.clone()),
+        false => {
+            let comment = match modular {
+                true => "Module path with dependency JARs and directories",
+                false => "Classpath with dependency JARs and directories",
+            };
+
+            variables.push_str(&format!("
+# {}
+{} = {}
+",
+comment, dependencies, java_data.classpath.join(":")));
+
+            let option = if modular { "--module-path" } else { "-cp" };
+
+            (format!(" {} $({})", option, dependencies), format!("{}:$({})", classes_dirs, dependencies))
+        }
+    };
+
+    if let Some(main_class) = &java_data.main_class {
+        let simple_name = java_data.get_artifact_name();
+
+        variables.push_str(&format!("
+# Main class and executable jar
+MAIN_CLASS = {}
+",
+main_class));
+
+        if let Some(main_module) = java_data.get_main_module().filter(|_| modular) {
+            // Every module is packaged into its own modular jar, named after the module
+            let module_jars: Vec<String> = java_data.modules.iter()
+                .map(|module| format!("{}/{}.jar", java_data::JAR_OUTPUT_DIR, module.name))
+                .collect();
+
+            variables.push_str(&format!("MAIN_MODULE = {}\nJAR_DIR = {}\nMODULE_JARS = {}\n",
+                main_module, java_data::JAR_OUTPUT_DIR, module_jars.join(" ")));
+            clean_files.push_str(" $(JAR_DIR)");
+        }
+        else {
+            variables.push_str(&format!("JAR_FILE = {}.jar\nMANIFEST = manifest.txt\n", simple_name));
+
+            // Directories in the manifest Class-Path have to end with a slash
+            if !java_data.classpath.is_empty() {
+                let manifest_entries: Vec<String> = java_data.classpath.iter()
+                    .map(|entry| match entry.ends_with(".jar") || entry.ends_with('/') {
+                        true => entry.clone(),
+                        false => format!("{}/", entry),
+                    })
+                    .collect();
+
+                variables.push_str(&format!("MANIFEST_CLASS_PATH = {}\n", manifest_entries.join(" ")));
+            }
+
+            clean_files.push_str(" $(MANIFEST) $(JAR_FILE)");
+        }
+    }
+
+    let test_classes = java_data.get_test_classes();
+    let has_tests = !test_classes.0.is_empty();
+
+    if has_tests {
+        variables.push_str(&format!("
+# Test sources and compiled test classes
+TEST_OUT_DIR = {}
+TEST_SOURCE = {}
+TEST_CLASSES_STAMP = test-classes.stamp
+TEST_SOURCES_LIST = test-sources.txt
+TEST_CLASS_NAMES = {}
+JUNIT_JAR = {}
+",
+java_data::TEST_OUTPUT_DIR, java_data.get_test_source_files(),
+test_classes, java_data.junit_jar.as_deref().unwrap_or(java_data::JUNIT_JAR)));
+
+        clean_files.push_str(" $(TEST_OUT_DIR) $(TEST_CLASSES_STAMP) $(TEST_SOURCES_LIST)");
+    }
+
+    // The native library is loaded from the project directory
+    let library_path = match native {
+        Some((native_data, library)) => {
+            variables.push_str(&format!("
+# Native library built from the C/C++ sources
+{}{}FLAGS = -g -c -Wall{}{}{}
+LFLAGS = {}
+{}NATIVE_OBJS = {}
+NATIVE_SOURCE = {}
+NATIVE_HEADER = {}
+{}NATIVE_LIB = lib{}.so
+LDLIBS = {}
+
+# JDK headers, and the headers generated by javac -h
+JNI_HEADER_DIR = jni-headers
+JAVA_HOME ?= $(shell dirname $$(dirname $$(readlink -f $$(which javac))))
+JNI_PLATFORM := $(shell uname -s | tr '[:upper:]' '[:lower:]')
+JNI_INCLUDE = -I$(JAVA_HOME)/include -I$(JAVA_HOME)/include/$(JNI_PLATFORM) -I$(JNI_HEADER_DIR)
+",
+generate_compiler_variables(native_data), generate_driver_variables(native_data, "NATIVE_OBJS"), get_pic_flag(native_data), get_library_flags(native_data),
+get_driver_compile_flags(native_data), get_link_flags(native_data), generate_tool_variables(native_data, &get_pic_flag(native_data)), native_data.get_cpp_compiled_files(),
+native_data.get_cpp_source_files(), get_header_files(native_data), generate_output_variables(native_data), library,
+get_ldlibs(native_data)));
+
+            clean_files.push_str(" $(NATIVE_OBJS) $(NATIVE_LIB) $(JNI_HEADER_DIR)");
+            clean_files.push_str(&get_output_clean_files(native_data));
+            " -Djava.library.path=."
+        }
+        None => "",
+    };
+
+    if java_data.doc || java_data.sources_jar {
+        variables.push_str("\n# Documentation and sources jar\n");
+
+        if java_data.doc {
+            variables.push_str(&format!("JAVADOC = javadoc\nDOC_DIR = {}\n", java_data::DOC_DIR));
+
+            // Packages are passed by name, unless some sources are in the default package
+            if let (Some(packages), false) = (java_data.get_packages(), modular) {
+                variables.push_str(&format!("DOC_PACKAGES = {}\n", packages));
+            }
+            clean_files.push_str(" $(DOC_DIR)");
+        }
+
+        if java_data.sources_jar && modular {
+            let jar_names: Vec<String> = java_data.get_packaged_sources().into_iter()
+                .map(|packaged| packaged.jar_name)
+                .collect();
+
+            variables.push_str(&format!("SOURCES_JARS = {}\n", jar_names.join(" ")));
+            clean_files.push_str(" $(SOURCES_JARS)");
+        }
+        else if java_data.sources_jar {
+            for packaged in java_data.get_packaged_sources() {
+                variables.push_str(&format!("SOURCES_JAR = {}\nSOURCES_JAR_DIR = {}\nSOURCES_JAR_FILES = {}\n",
+                    packaged.jar_name, packaged.root, packaged.files));
+            }
+            clean_files.push_str(" $(SOURCES_JAR)");
+        }
+    }
+
+    let native_lib = match native {
+        Some(_) => " $(NATIVE_LIB)",
+        None => "",
+    };
+
+    let mut rules = String::new();
+
+    if modular {
+        rules.push_str(&format!("
+# Compilation rule, javac finds the sources of each module in the module source path
+$(CLASSES_STAMP): $(SOURCE)
+\t@mkdir -p $(OUT_DIR)
+\t$(JC) $(JFLAGS) -d $(OUT_DIR){}{} $(foreach path,$(MODULE_SOURCE_PATH),--module-source-path $(path)) --module $(MODULES)
+\t@touch $(CLASSES_STAMP)
+", compile_path, header_option));
+    }
+    else {
+        rules.push_str(SOURCES_LIST_RULE);
+        rules.push_str(&format!("
+# Compilation rule, a single javac invocation compiles all classes
+$(CLASSES_STAMP): $(SOURCES_LIST)
+\t@mkdir -p $(OUT_DIR)
+\t$(JC) $(JFLAGS) -d $(OUT_DIR){}{} -sourcepath $(SRC_DIR) @$(SOURCES_LIST)
+\t@touch $(CLASSES_STAMP)
+", compile_path, header_option));
+    }
+
+    if let Some((native_data, _)) = native {
+        rules.push_str(&format!("
+# Linking rule for the native library
+$(NATIVE_LIB): $(NATIVE_OBJS)
+\t{} -shared $(NATIVE_OBJS) -o $(NATIVE_LIB) $(LFLAGS) $(LDLIBS)
+
+# The native sources include the headers generated by javac
+$(NATIVE_OBJS): $(CLASSES_STAMP)
+
+# Compilation rules for the native sources
+", get_linker(native_data)));
+
+        for extension in get_rule_extensions(native_data) {
+            rules.push_str(&generate_pattern_rule(native_data, extension, "$(NATIVE_HEADER)", " $(JNI_INCLUDE)"));
+        }
+        rules.push_str(&generate_dependency_rules(native_data));
+    }
+
+    if !resources.is_empty() {
+        rules.push_str(&format!("
+# Copy the resources into the output directory
+{}/%: $(RESOURCE_DIR)/%
+\t@mkdir -p $(dir $@)
+\tcp $< $@
+", java_data.get_resource_output_dir().replacen(&java_data.output_dir, "$(OUT_DIR)", 1)));
+    }
+
+    if java_data.main_class.is_some() && modular {
+        let module_path = match java_data.classpath.is_empty() {
+            true => "$(OUT_DIR)",
+            false => "$(OUT_DIR):$(MODULE_PATH)",
+        };
+
+        rules.push_str(&format!("
+# Modular jars, one per module, run with java --module-path $(JAR_DIR) --module $(MAIN_MODULE)
+jar: $(MODULE_JARS)
+
+$(JAR_DIR)/%.jar: $(CLASSES_STAMP){0}
+\t@mkdir -p $(JAR_DIR)
+\t$(JAR) --create --file $@ -C $(OUT_DIR)/$* .
+
+# The jar of the main module launches the main class
+$(JAR_DIR)/$(MAIN_MODULE).jar: $(CLASSES_STAMP){0}
+\t@mkdir -p $(JAR_DIR)
+\t$(JAR) --create --file $@ --main-class $(MAIN_CLASS) -C $(OUT_DIR)/$(MAIN_MODULE) .
+
+# Run the main class from its module
+run: $(CLASSES_STAMP){0}{1}
+\t$(JAVA){2} --module-path {3} --module $(MAIN_MODULE)/$(MAIN_CLASS)
+",
+resources, native_lib, library_path, module_path));
+    }
+    else if java_data.main_class.is_some() {
+        // Manifest lines are limited to 72 bytes, so every Class-Path entry goes on its own continuation line
+        let manifest_class_path = match java_data.classpath.is_empty() {
+            true => "",
+            false => "\n\t@echo \"Class-Path: \" >> $(MANIFEST)\n\t@printf '  %s\\n' $(MANIFEST_CLASS_PATH) >> $(MANIFEST)",
+        };
+
+        rules.push_str(&format!("
+# Executable jar with a generated manifest
+jar: $(JAR_FILE)
+
+$(MANIFEST): $(MAKEFILE_LIST)
+\t@echo \"Main-Class: $(MAIN_CLASS)\" > $(MANIFEST){0}
+
+$(JAR_FILE): $(CLASSES_STAMP){1} $(MANIFEST)
+\t$(JAR) cfm $(JAR_FILE) $(MANIFEST) -C $(OUT_DIR) .
+
+# Run the main class
+run: $(CLASSES_STAMP){1}{2}
+\t$(JAVA){3} -cp {4} $(MAIN_CLASS)
+",
+manifest_class_path, resources, native_lib, library_path, run_classpath));
+    }
+
+    if java_data.doc {
+        let doc_sources = match (java_data.get_packages(), modular) {
+            (_, true) => "$(foreach path,$(MODULE_SOURCE_PATH),--module-source-path $(path)) --module $(MODULES)",
+            (Some(_), false) => "-sourcepath $(SRC_DIR) $(DOC_PACKAGES)",
+            (None, false) => "$(SOURCE)",
+        };
+
+        rules.push_str(&format!("
+# Generate the documentation with javadoc
+doc: $(DOC_DIR)/index.html
+
+$(DOC_DIR)/index.html: $(SOURCE)
+\t$(JAVADOC) -d $(DOC_DIR){} {}
+", compile_path, doc_sources));
+    }
+
+    if java_data.sources_jar && modular {
+        rules.push_str("
+# Jars with the source files of every module, laid out by package
+sources-jar: $(SOURCES_JARS)
+");
+
+        for packaged in java_data.get_packaged_sources() {
+            let files: Vec<String> = packaged.files.0.iter()
+                .map(|file| format!("-C {} {}", packaged.root, file))
+                .collect();
+
+            rules.push_str(&format!("\n{}: $(SOURCE)\n\t$(JAR) cf $@ {}\n", packaged.jar_name, files.join(" ")));
+        }
+    }
+    else if java_data.sources_jar {
+        rules.push_str("
+# Jar with the source files, laid out by package
+sources-jar: $(SOURCES_JAR)
+
+$(SOURCES_JAR): $(SOURCE)
+\t$(JAR) cf $(SOURCES_JAR) $(foreach file,$(SOURCES_JAR_FILES),-C $(SOURCES_JAR_DIR) $(file))
+");
+    }
+
+    if has_tests {
+        // Tests are compiled and run on the classpath, even in modular projects
+        let test_compile_classpath = match java_data.classpath.is_empty() {
+            true => format!("{}:$(JUNIT_JAR)", classes_dirs),
+            false => format!("{}:$(JUNIT_JAR):$({})", classes_dirs, dependencies),
+        };
+
+        rules.push_str(&format!("
+# List of test source files passed to javac
+$(TEST_SOURCES_LIST): $(TEST_SOURCE)
+\t@printf '%s\\n' $(TEST_SOURCE) > $(TEST_SOURCES_LIST)
+
+# Compilation rule for tests, against the main classes
+$(TEST_CLASSES_STAMP): $(CLASSES_STAMP) $(TEST_SOURCES_LIST)
+\t@mkdir -p $(TEST_OUT_DIR)
+\t$(JC) $(JFLAGS) -d $(TEST_OUT_DIR) -cp {} @$(TEST_SOURCES_LIST)
+\t@touch $(TEST_CLASSES_STAMP)
+
+# Run the tests with the JUnit console launcher
+test: $(TEST_CLASSES_STAMP){}{}
+\t$(JAVA){} -jar $(JUNIT_JAR) --class-path $(TEST_OUT_DIR):{} $(addprefix --select-class=,$(TEST_CLASS_NAMES))
+",
+test_compile_classpath, resources, native_lib, library_path, run_classpath));
+    }
+
+    MakefileSections {
+        variables,
+        default_prerequisites: format!("$(CLASSES_STAMP){}{}", resources, native_lib),
+        rules,
+        clean_files,
+    }
+}
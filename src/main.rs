@@ -1,24 +1,26 @@
-use makewiz::build_data;
-use makewiz::cli::{self, Commands};
+use makewiz::build_data::{self, BuildData, Language};
+use makewiz::compiler_preset::{CompilerDriver, CompilerPreset};
+use makewiz::java_data::JavaData;
+use makewiz::cli::{self, Commands, CLI, JavaArgs, JniArgs, OutputArgs};
+use makewiz::{diff, output, provenance};
 use makewiz::user_config::{self, UserConfig};
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use directories::ProjectDirs;
 
+use std::env;
 use std::fs;
+use std::iter;
+use std::path::Path;
 use std::process;
 
-fn main() {
-    let paths_to_files = fs::read_dir(".").unwrap_or_else(|err| {
-        eprintln!("Error: {}", err);
-        process::exit(1);
-    });
+/// Exit code returned by `--check` when the Makefile is out of date.
+const STALE_MAKEFILE_EXIT_CODE: i32 = 2;
 
-    let mut file_names = build_data::BuildData::extract_names(paths_to_files).unwrap_or_else(|err| {
-        eprintln!("Error: {}", err);
-        process::exit(1);
-    });
+/// Name of the JNI library if the Java sources don't load one.
+const DEFAULT_NATIVE_LIBRARY: &str = "native";
 
+fn main() {
     // Set directory where the config file will be placed
     let config_dir = ProjectDirs::from("", "",  "makewiz")
         .expect("Valid home directory path for the config file couldn't be retrieved");
@@ -36,19 +38,64 @@ fn main() {
     // macOS:   /Users/<username>/Library/Application Support/makewiz/config.toml
     let config = UserConfig::get_current_config(&config_path);
 
-    // Set config values to later write them to the Makefile
-    file_names.compiler = config.compiler_name;
-    file_names.executable = config.executable_name;
-
     // Get user arguments
-    let args = cli::CLI::parse();
+    let mut args = cli::CLI::parse();
+    let mut recorded_args = OutputArgs::strip_from(&env::args().skip(1).collect::<Vec<String>>());
+
+    // Regenerate with the arguments recorded in the existing Makefile.
+    // A plain --check, without options changing the Makefile, checks it against them too
+    let recorded_invocation = match &args.command {
+        Some(Commands::Regen) => Some(read_recorded_args(&args.output_args.output)),
+        None if args.output_args.check && recorded_args.is_empty() => fs::read_to_string(&args.output_args.output).ok()
+            .and_then(|contents| provenance::parse_invocation(&contents)),
+        _ => None,
+    };
+
+    // The recorded invocation already contains the options that were taken from the config
+    let config = match recorded_invocation.is_some() {
+        true => UserConfig::default(),
+        false => config,
+    };
+
+    if let Some(invocation) = recorded_invocation {
+        recorded_args = invocation;
+
+        let program_name = iter::once(String::from("makewiz"));
+        let mut recorded = cli::CLI::try_parse_from(program_name.chain(recorded_args.iter().cloned()))
+            .unwrap_or_else(|err| err.exit());
+
+        if let Some(Commands::Regen) = &recorded.command {
+            eprintln!("Error: The recorded invocation can't be another regen");
+            process::exit(1);
+        }
+
+        recorded.output_args = args.output_args;
+        args = recorded;
+    }
 
     // Check if both subcommand and flags are provided
-    if args.subcommands_provided() && args.flags_provided() {
+    if args.subcommands_provided() && !args.native_subcommand_provided() && args.flags_provided() {
         eprintln!("Error: Cannot use subcommands and flags at the same time!");
         std::process::exit(1);
     }
 
+    let paths_to_files = fs::read_dir(".").unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    });
+
+    // Custom extensions from the config and the arguments are recognized along with the built-in ones
+    let mut extensions = config.extensions.clone();
+    extensions.extend(args.extensions.iter().cloned());
+    let mut file_names = build_data::BuildData::extract_names_with_extensions(paths_to_files, extensions).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    });
+
+    // Set config values to later write them to the Makefile
+    file_names.compiler = config.compiler_name.clone();
+    file_names.executable = config.executable_name.clone();
+
     // Handle options
     if let Some(executable) = &args.executable {
         file_names.executable = executable.clone();
@@ -58,18 +105,24 @@ fn main() {
         file_names.compiler = compiler.clone();
     }
 
+    if let Some(objc_runtime) = args.objc_runtime {
+        file_names.objc_runtime = objc_runtime;
+    }
+
+    // Only C/C++ Makefiles use the compiler driver
+    if !args.subcommands_provided() || args.native_subcommand_provided() {
+        apply_compiler_preset(&args, &config, &mut file_names);
+    }
+
     // Handle flags
     let (lflags, ldlibs) = args.parse_flags();
     file_names.lflags = lflags;
     file_names.ldlibs = ldlibs;
 
     // Handle subcommands
-    let mut java = false;
-    match &args.command {
-        Some(command) => match command {
-            Commands::Java => {
-                java = true;
-            },
+    if let Some(command) = &args.command {
+        match command {
+            Commands::Java(_) | Commands::Jni(_) | Commands::Mixed(_) | Commands::Kotlin(_) | Commands::Scala(_) => { },
 
             Commands::SetCompiler(compiler) => {
                 UserConfig::update_config(user_config::Attribute::CompilerName(compiler.name.clone()), &config_path);
@@ -81,20 +134,397 @@ fn main() {
                 return;
             },
 
+            Commands::SetPreset(preset) => {
+                UserConfig::update_config(user_config::Attribute::CompilerPreset(preset.preset), &config_path);
+                return;
+            },
+
+            Commands::SetJava(java_config) => {
+                for attribute in java_config.get_attributes() {
+                    UserConfig::update_config(attribute, &config_path);
+                }
+                return;
+            },
+
+            Commands::SetExtension(extension) => {
+                let name = extension.extension.trim_start_matches('.').to_string();
+                let attribute = user_config::Attribute::Extension(name, extension.role);
+                UserConfig::update_config(attribute, &config_path);
+                return;
+            },
+
             Commands::Default => {
                 UserConfig::print_config_values(&config_path);
                 return;
-            }
-        },
-        None => { }
+            },
+
+            Commands::Regen => { }
+        }
     }
 
     // Create the makefile
-    let makefile = match java {
-        true => { makewiz::generate_java_makefile(&file_names) }
-        false => { makewiz::generate_makefile(&file_names) }
+    let (makefile, java_config_args) = match &args.command {
+        Some(Commands::Java(java_args)) => {
+            let (java_data, java_config_args) = prepare_java_data(java_args, &config);
+
+            (makewiz::generate_java_makefile(&java_data), java_config_args)
+        }
+        Some(Commands::Jni(jni_args)) => {
+            let (java_data, java_config_args) = prepare_java_data(&jni_args.java_args, &config);
+            let library = choose_native_library(jni_args, &java_data);
+
+            (makewiz::generate_jni_makefile(&java_data, &file_names, &library), java_config_args)
+        }
+        Some(Commands::Mixed(java_args)) => {
+            let (java_data, java_config_args) = prepare_java_data(java_args, &config);
+
+            (makewiz::generate_mixed_makefile(&file_names, &java_data), java_config_args)
+        }
+        Some(Commands::Kotlin(java_args)) => {
+            let mut java_data = scan_java_sources();
+            java_data.extract_kotlin_sources(Path::new(".")).unwrap_or_else(|err| {
+                eprintln!("Error: {}", err);
+                process::exit(1);
+            });
+            let java_config_args = configure_java_data(&mut java_data, java_args, &config);
+
+            (makewiz::generate_kotlin_makefile(&java_data), java_config_args)
+        }
+        Some(Commands::Scala(java_args)) => {
+            let mut java_data = scan_java_sources();
+            java_data.extract_scala_sources(Path::new(".")).unwrap_or_else(|err| {
+                eprintln!("Error: {}", err);
+                process::exit(1);
+            });
+            let java_config_args = configure_java_data(&mut java_data, java_args, &config);
+
+            (makewiz::generate_scala_makefile(&java_data), java_config_args)
+        }
+        _ => { (makewiz::generate_makefile(&file_names), Vec::new()) }
+    };
+
+    // Options taken from the config are recorded too, so regenerating doesn't depend on the config
+    let native_config_args = match !args.subcommands_provided() || args.native_subcommand_provided() {
+        true => get_native_config_args(&args, &config),
+        false => Vec::new(),
     };
+    let recorded_args = add_config_args(recorded_args, native_config_args, java_config_args);
+
+    let makefile = provenance::generate_header(&recorded_args) + &makefile;
+
+    write_makefile(&args.output_args, &makefile);
+}
+
+/// Chooses the compiler preset and sets up the compiler driver with the target and sanitizers.
+///
+/// A preset from the arguments takes precedence over a compiler name from the arguments,
+/// which in turn replaces the preset from the config. Targets and sanitizers need a preset,
+/// so one is detected from the compiler name if none was chosen.
+fn apply_compiler_preset(args: &CLI, config: &UserConfig, file_names: &mut BuildData) {
+    let mut preset = match args.compiler {
+        Some(_) => None,
+        None => config.compiler_preset,
+    };
+    if args.preset.is_some() {
+        preset = args.preset;
+    }
+
+    if preset.is_none() && (args.target.is_some() || !args.sanitize.is_empty()) {
+        preset = Some(CompilerPreset::detect(&file_names.compiler).unwrap_or_else(|| {
+            eprintln!("Error: Unknown compiler {}, use --preset to choose one for --target and --sanitize", file_names.compiler);
+            process::exit(1);
+        }));
+    }
+
+    let Some(preset) = preset else { return; };
+
+    let driver = CompilerDriver {
+        preset,
+        target: args.target.clone(),
+        sanitizers: args.sanitize.clone(),
+    };
+
+    let has_cpp = file_names.has_sources(Language::Cpp) || file_names.has_sources(Language::ObjCpp) || file_names.has_sources(Language::Proto);
+    let has_host_tools = file_names.has_sources(Language::Assembly) || file_names.has_sources(Language::Fortran);
+    driver.validate(has_cpp, has_host_tools).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    });
+
+    file_names.driver = Some(driver);
+}
+
+/// Returns the C/C++ options taken from the config, as arguments for the provenance header.
+///
+/// Only the values that aren't set by the arguments and differ from the defaults are returned.
+fn get_native_config_args(args: &CLI, config: &UserConfig) -> Vec<String> {
+    let defaults = UserConfig::default();
+    let mut config_args = Vec::new();
+
+    if args.compiler.is_none() && config.compiler_name != defaults.compiler_name {
+        config_args.extend([String::from("-c"), config.compiler_name.clone()]);
+    }
+    if args.executable.is_none() && config.executable_name != defaults.executable_name {
+        config_args.extend([String::from("-e"), config.executable_name.clone()]);
+    }
 
-    fs::write("./Makefile", makefile).expect("Unable to create a Makefile");
+    // The preset from the config is only used without a compiler from the arguments
+    if let (None, None, Some(preset)) = (&args.compiler, args.preset, config.compiler_preset) {
+        config_args.extend([String::from("--preset"), value_name(preset)]);
+    }
+
+    for (extension, role) in &config.extensions {
+        if !args.extensions.iter().any(|(name, _)| name == extension) {
+            config_args.extend([String::from("--extension"), format!("{}={}", extension, value_name(*role))]);
+        }
+    }
+
+    config_args
+}
+
+/// Returns the name of a value on the command line, e.g. `zig` for `CompilerPreset::Zig`.
+fn value_name<T: ValueEnum>(value: T) -> String {
+    value.to_possible_value().map(|value| value.get_name().to_string()).unwrap_or_default()
+}
+
+/// Adds the options taken from the config to the recorded arguments.
+///
+/// The C/C++ options go before the subcommand, and the Java options right after its name,
+/// so the configured classpath stays in front of the one from the arguments.
+fn add_config_args(recorded_args: Vec<String>, native_config_args: Vec<String>, java_config_args: Vec<String>) -> Vec<String> {
+    let mut args = native_config_args;
+    let java_start = CLI::find_subcommand(&recorded_args).map_or(recorded_args.len(), |index| index + 1);
+
+    args.extend_from_slice(&recorded_args[..java_start]);
+    args.extend(java_config_args);
+    args.extend_from_slice(&recorded_args[java_start..]);
+
+    args
+}
+
+/// Scans the current directory for Java sources and applies the options from the arguments and the config.
+///
+/// # Returns
+///
+/// The scanned sources, and the options taken from the config, as arguments for the provenance header.
+fn prepare_java_data(java_args: &JavaArgs, config: &UserConfig) -> (JavaData, Vec<String>) {
+    let mut java_data = scan_java_sources();
+    let config_args = configure_java_data(&mut java_data, java_args, config);
+
+    (java_data, config_args)
+}
+
+/// Scans the current directory for Java sources.
+fn scan_java_sources() -> JavaData {
+    JavaData::extract_sources(Path::new(".")).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    })
+}
+
+/// Applies the options from the arguments and the config to the scanned sources.
+///
+/// # Returns
+///
+/// The options taken from the config, as arguments for the provenance header.
+fn configure_java_data(java_data: &mut JavaData, java_args: &JavaArgs, config: &UserConfig) -> Vec<String> {
+    let mut config_args = Vec::new();
+
+    for entry in &config.java_classpath {
+        config_args.extend([String::from("--classpath"), entry.clone()]);
+    }
+    java_data.add_to_classpath(&config.java_classpath);
+    java_data.add_to_classpath(&java_args.classpath);
+
+    // A JUnit JAR found in lib/ takes precedence over the default from the config
+    if let Some(path) = &java_args.junit_jar {
+        java_data.junit_jar = Some(path.clone());
+    }
+    else if java_data.junit_jar.is_none() {
+        if config.junit_jar != UserConfig::default().junit_jar {
+            config_args.extend([String::from("--junit-jar"), config.junit_jar.clone()]);
+        }
+        java_data.junit_jar = Some(config.junit_jar.clone());
+    }
+
+    java_data.release = choose_java_option("--release", &java_args.release, &config.java_release, &mut config_args);
+    java_data.lint = choose_java_option("--lint", &java_args.lint, &config.java_lint, &mut config_args);
+    java_data.werror = choose_java_flag("--werror", java_args.werror, config.java_werror, &mut config_args);
+    java_data.encoding = choose_java_option("--encoding", &java_args.encoding, &config.java_encoding, &mut config_args);
+    java_data.extra_flags = choose_java_option("--javac-flags", &java_args.javac_flags, &config.javac_flags, &mut config_args);
+    java_data.doc = choose_java_flag("--doc", java_args.doc, config.java_doc, &mut config_args);
+    java_data.sources_jar = choose_java_flag("--sources-jar", java_args.sources_jar, config.java_sources_jar, &mut config_args);
+
+    // A configured resources directory replaces the detected resources/
+    let resource_dir = choose_java_option("--resources", &java_args.resources, &config.java_resources, &mut config_args);
+    if !resource_dir.is_empty() {
+        java_data.set_resource_dir(Path::new("."), &resource_dir).unwrap_or_else(|err| {
+            eprintln!("Error: {}: {}", resource_dir, err);
+            process::exit(1);
+        });
+    }
+
+    java_data.main_class = match &java_args.main_class {
+        Some(main_class) => {
+            check_main_class(java_data, main_class);
+            Some(main_class.clone())
+        }
+        None => detect_main_class(java_data),
+    };
+
+    let external_requires = java_data.get_external_requires();
+    if !external_requires.is_empty() && java_data.classpath.is_empty() {
+        eprintln!("Warning: Required modules {} weren't found, add them to lib/ or use --classpath", external_requires.join(", "));
+    }
+
+    config_args
+}
+
+/// Returns the value of a Java option from the arguments, or the configured one, which is recorded if it's set.
+fn choose_java_option(option: &str, value: &Option<String>, configured: &str, config_args: &mut Vec<String>) -> String {
+    match value {
+        Some(value) => value.clone(),
+        None => {
+            if !configured.is_empty() {
+                config_args.extend([String::from(option), String::from(configured)]);
+            }
+            String::from(configured)
+        }
+    }
+}
+
+/// Returns whether a Java flag is enabled by the arguments or the config, recording it if only the config enables it.
+fn choose_java_flag(option: &str, value: bool, configured: bool, config_args: &mut Vec<String>) -> bool {
+    if !value && configured {
+        config_args.push(String::from(option));
+    }
+
+    value || configured
+}
+
+/// Warns if the main class chosen with `--main-class` isn't one of the scanned classes with a main method.
+fn check_main_class(java_data: &JavaData, main_class: &str) {
+    if java_data.has_main_class(main_class) {
+        return;
+    }
+
+    let main_classes = java_data.get_main_classes();
+    let found = match main_classes.is_empty() {
+        true => String::from("none"),
+        false => main_classes.join(", "),
+    };
+    eprintln!("Warning: {} wasn't found among the classes with a main method (found: {}), check --main-class", main_class, found);
+}
+
+/// Picks the main class if exactly one class has a main method.
+fn detect_main_class(java_data: &JavaData) -> Option<String> {
+    let main_classes = java_data.get_main_classes();
+
+    if main_classes.len() > 1 {
+        eprintln!("Warning: Found multiple main classes ({}), use --main-class to choose one", main_classes.join(", "));
+    }
+
+    match main_classes.as_slice() {
+        [main_class] => Some(main_class.clone()),
+        _ => None,
+    }
+}
+
+/// Picks the name of the native library, from the arguments or the `System.loadLibrary` calls.
+fn choose_native_library(jni_args: &JniArgs, java_data: &JavaData) -> String {
+    if let Some(library) = &jni_args.library {
+        return library.clone();
+    }
+
+    match java_data.get_loaded_libraries().as_slice() {
+        [library] => library.clone(),
+        [] => String::from(DEFAULT_NATIVE_LIBRARY),
+        libraries => {
+            eprintln!("Warning: Found multiple loaded libraries ({}), use --library to choose one", libraries.join(", "));
+            libraries[0].clone()
+        }
+    }
+}
+
+/// Reads the arguments recorded in the header of the Makefile at the given path.
+fn read_recorded_args(path: &Path) -> Vec<String> {
+    let contents = fs::read_to_string(path).unwrap_or_else(|err| {
+        eprintln!("Error: {}: {}", path.display(), err);
+        process::exit(1);
+    });
+
+    provenance::parse_invocation(&contents).unwrap_or_else(|| {
+        eprintln!("Error: {} doesn't contain a valid MakeWiz header", path.display());
+        process::exit(1);
+    })
+}
+
+/// Writes the Makefile to the chosen output, or prints it/its diff, depending on the arguments.
+fn write_makefile(args: &OutputArgs, makefile: &str) {
+    let path = args.output.as_path();
+
+    // Only replace the block managed by MakeWiz, keeping hand-written rules around it
+    let existing = fs::read_to_string(path).ok();
+    let contents = output::merge_with_existing(existing.as_deref(), makefile);
+
+    if args.stdout {
+        print!("{}", contents);
+        return;
+    }
+
+    if args.diff {
+        let name = path.display().to_string();
+        print!("{}", diff::unified_diff(existing.as_deref().unwrap_or(""), &contents, &name, &name));
+        return;
+    }
+
+    if args.check {
+        check_makefile(&path.display().to_string(), existing.as_deref(), &contents);
+        return;
+    }
+
+    if let Some(existing) = &existing {
+        if !args.force && !output::can_overwrite(existing) {
+            eprintln!("Error: {} wasn't generated by MakeWiz, use --force to overwrite it", path.display());
+            process::exit(1);
+        }
+
+        if args.backup {
+            let backup_path = output::write_backup(path).unwrap_or_else(|err| {
+                eprintln!("Error: {}", err);
+                process::exit(1);
+            });
+            println!("Backup saved to {}", backup_path.display());
+        }
+    }
+
+    fs::write(path, contents).unwrap_or_else(|err| {
+        eprintln!("Error: unable to create {}: {}", path.display(), err);
+        process::exit(1);
+    });
     println!("Makefile successfully created");
-}
\ No newline at end of file
+}
+
+/// Compares the regenerated Makefile with the existing one and exits with
+/// `STALE_MAKEFILE_EXIT_CODE` if they differ. The MakeWiz version in the header isn't compared.
+fn check_makefile(name: &str, existing: Option<&str>, contents: &str) {
+    if existing.map(provenance::strip_version) == Some(provenance::strip_version(contents)) {
+        println!("{} is up to date", name);
+        return;
+    }
+
+    println!("{} is out of date", name);
+
+    let (added, removed) = output::compare_source_files(existing.unwrap_or(""), contents);
+    if !added.is_empty() {
+        println!("  added sources: {}", added.join(" "));
+    }
+    if !removed.is_empty() {
+        println!("  removed sources: {}", removed.join(" "));
+    }
+    if added.is_empty() && removed.is_empty() {
+        println!("  source files are the same, but the generated content changed");
+    }
+
+    process::exit(STALE_MAKEFILE_EXIT_CODE);
+}
@@ -0,0 +1,298 @@
+//! Handles writing the generated Makefile.
+//!
+//! Generated content is wrapped in `# BEGIN MAKEWIZ` / `# END MAKEWIZ` marker comments.
+//! When a Makefile with such a managed block already exists, only the block is replaced,
+//! so rules and variables written by hand outside of it are kept intact.
+//!
+//! A Makefile without a managed block is treated as hand-written, and is only replaced
+//! when the user explicitly asks for it. A timestamped backup can be saved beforehand.
+
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Marker comment opening the block managed by MakeWiz.
+pub const BEGIN_MARKER: &str = "# BEGIN MAKEWIZ";
+
+/// Marker comment closing the block managed by MakeWiz.
+pub const END_MARKER: &str = "# END MAKEWIZ";
+
+/// Wraps the generated Makefile content in the MakeWiz marker comments.
+///
+/// # Arguments
+///
+/// * `makefile` - The generated Makefile content.
+///
+/// # Returns
+///
+/// A `String` containing the managed block.
+pub fn wrap_in_markers(makefile: &str) -> String {
+    let mut block = format!("{}\n{}", BEGIN_MARKER, makefile);
+    if !block.ends_with('\n') {
+        block.push('\n');
+    }
+    block.push_str(END_MARKER);
+    block.push('\n');
+
+    block
+}
+
+/// Merges newly generated content with the contents of an existing Makefile.
+///
+/// If the existing Makefile contains a managed block, only that block is replaced.
+/// Otherwise the whole file is replaced with a fresh managed block.
+///
+/// # Arguments
+///
+/// * `existing` - The contents of the existing Makefile, if there is one.
+/// * `makefile` - The generated Makefile content.
+///
+/// # Returns
+///
+/// A `String` containing the full Makefile that should be written.
+pub fn merge_with_existing(existing: Option<&str>, makefile: &str) -> String {
+    let block = wrap_in_markers(makefile);
+
+    match existing.and_then(|contents| find_managed_block(contents).map(|range| (contents, range))) {
+        Some((contents, (start, end))) => format!("{}{}{}", &contents[..start], block, &contents[end..]),
+        None => block,
+    }
+}
+
+/// Returns the block managed by MakeWiz, including its marker comments.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the Makefile.
+///
+/// # Returns
+///
+/// An `Option` containing the managed block, or `None` if the Makefile has no managed block.
+pub fn get_managed_block(contents: &str) -> Option<&str> {
+    find_managed_block(contents).map(|(start, end)| &contents[start..end])
+}
+
+/// Checks if the given Makefile contents contain a block managed by MakeWiz.
+pub fn is_managed(contents: &str) -> bool {
+    find_managed_block(contents).is_some()
+}
+
+/// Checks if the given Makefile contents may be replaced without the user's permission.
+///
+/// Empty files and files containing a managed block can always be replaced.
+pub fn can_overwrite(contents: &str) -> bool {
+    contents.trim().is_empty() || is_managed(contents)
+}
+
+/// Saves a copy of the given file next to it, with the current UTC time in its name.
+/// Backups made within the same second get a numbered suffix, so an existing backup is never overwritten.
+///
+/// # Arguments
+///
+/// * `path` - The path to the file to back up.
+///
+/// # Returns
+///
+/// A `Result` containing the path to the created backup or an error.
+pub fn write_backup(path: &Path) -> io::Result<PathBuf> {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0);
+    let contents = fs::read(path)?;
+
+    for attempt in 0.. {
+        let backup_path = backup_path(path, seconds, attempt);
+
+        // Only create the backup if no file with its name exists yet
+        match OpenOptions::new().write(true).create_new(true).open(&backup_path) {
+            Ok(mut file) => {
+                file.write_all(&contents)?;
+                return Ok(backup_path);
+            }
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    unreachable!()
+}
+
+fn backup_path(path: &Path, seconds: u64, attempt: u32) -> PathBuf {
+    let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or("Makefile");
+
+    match attempt {
+        0 => path.with_file_name(format!("{}.{}.bak", file_name, format_timestamp(seconds))),
+        _ => path.with_file_name(format!("{}.{}-{}.bak", file_name, format_timestamp(seconds), attempt)),
+    }
+}
+
+/// Formats seconds since the Unix epoch as a `YYYYMMDD-HHMMSS` UTC timestamp.
+fn format_timestamp(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let time = seconds % 86_400;
+
+    // Converts days since the epoch to a civil date (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}{:02}{:02}-{:02}{:02}{:02}", year, month, day, time / 3_600, time % 3_600 / 60, time % 60)
+}
+
+/// Compares the source files listed in two Makefiles.
+///
+/// Source files are read from every variable whose name ends with `SOURCE`.
+///
+/// # Arguments
+///
+/// * `old` - The contents of the current Makefile.
+/// * `new` - The contents of the regenerated Makefile.
+///
+/// # Returns
+///
+/// A tuple containing the added and the removed source files.
+pub fn compare_source_files(old: &str, new: &str) -> (Vec<String>, Vec<String>) {
+    let old_files = source_files(old);
+    let new_files = source_files(new);
+
+    let added = new_files.iter().filter(|file| !old_files.contains(file)).cloned().collect();
+    let removed = old_files.iter().filter(|file| !new_files.contains(file)).cloned().collect();
+
+    (added, removed)
+}
+
+fn source_files(contents: &str) -> Vec<String> {
+    let mut files = Vec::new();
+
+    for line in contents.lines() {
+        if let Some((name, value)) = line.split_once('=') {
+            if name.trim().ends_with("SOURCE") {
+                files.extend(value.split_whitespace().map(String::from));
+            }
+        }
+    }
+
+    files
+}
+
+/// Finds the byte range of the managed block, including both marker lines.
+fn find_managed_block(contents: &str) -> Option<(usize, usize)> {
+    let mut start = None;
+    let mut offset = 0;
+
+    for line in contents.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+
+        if start.is_none() && trimmed == BEGIN_MARKER {
+            start = Some(offset);
+        }
+        else if trimmed == END_MARKER {
+            if let Some(start) = start {
+                return Some((start, offset + line.len()));
+            }
+        }
+
+        offset += line.len();
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn merging_without_existing_makefile() {
+        let expected = "# BEGIN MAKEWIZ\nall: main\n# END MAKEWIZ\n";
+
+        assert_eq!(expected, merge_with_existing(None, "all: main\n"));
+    }
+
+    #[test]
+    fn merging_keeps_hand_written_rules() {
+        let existing = "\
+CUSTOM = 1
+
+# BEGIN MAKEWIZ
+all: old
+# END MAKEWIZ
+
+deploy:
+\tscp main server:\n";
+        let expected = "\
+CUSTOM = 1
+
+# BEGIN MAKEWIZ
+all: new
+# END MAKEWIZ
+
+deploy:
+\tscp main server:\n";
+
+        assert_eq!(expected, merge_with_existing(Some(existing), "all: new\n"));
+    }
+
+    #[test]
+    fn merging_replaces_unmanaged_makefile() {
+        let existing = "all:\n\techo hand written\n";
+        let expected = "# BEGIN MAKEWIZ\nall: new\n# END MAKEWIZ\n";
+
+        assert!(!is_managed(existing));
+        assert_eq!(expected, merge_with_existing(Some(existing), "all: new\n"));
+    }
+
+    #[test]
+    fn unterminated_block_is_not_managed() {
+        assert!(!is_managed("# BEGIN MAKEWIZ\nall: main\n"));
+    }
+
+    #[test]
+    fn comparing_source_files() {
+        let old = "SOURCE = a.cpp b.cpp\nHEADER = a.hpp\n";
+        let new = "SOURCE = a.cpp c.cpp\nHEADER = c.hpp\n";
+
+        let (added, removed) = compare_source_files(old, new);
+
+        assert_eq!(vec![String::from("c.cpp")], added);
+        assert_eq!(vec![String::from("b.cpp")], removed);
+    }
+
+    #[test]
+    fn overwriting_protection() {
+        assert!(can_overwrite(""));
+        assert!(can_overwrite("# BEGIN MAKEWIZ\nall: main\n# END MAKEWIZ\n"));
+        assert!(!can_overwrite("all:\n\techo hand written\n"));
+    }
+
+    #[test]
+    fn backup_path_contains_timestamp() {
+        assert_eq!(PathBuf::from("./Makefile.19700101-000000.bak"), backup_path(Path::new("./Makefile"), 0, 0));
+        assert_eq!(PathBuf::from("dir/Makefile.20241019-153005.bak"), backup_path(Path::new("dir/Makefile"), 1_729_351_805, 0));
+        assert_eq!(PathBuf::from("./Makefile.19700101-000000-2.bak"), backup_path(Path::new("./Makefile"), 0, 2));
+    }
+
+    #[test]
+    fn backups_within_the_same_second_are_kept() {
+        let dir = std::env::temp_dir().join(format!("makewiz-backup-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let makefile = dir.join("Makefile");
+
+        fs::write(&makefile, "first").unwrap();
+        let first = write_backup(&makefile).unwrap();
+        fs::write(&makefile, "second").unwrap();
+        let second = write_backup(&makefile).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!("first", fs::read_to_string(&first).unwrap());
+        assert_eq!("second", fs::read_to_string(&second).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
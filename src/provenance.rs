@@ -0,0 +1,172 @@
+//! Records how a Makefile was generated.
+//!
+//! Every generated Makefile starts with a header containing the MakeWiz version and the
+//! arguments it was generated with, for example:
+//!
+//! ```text
+//! # Generated by MakeWiz 0.8.0
+//! # Invocation: makewiz -c clang -m
+//! ```
+//!
+//! `makewiz regen` reads the invocation back to regenerate the Makefile with the same options.
+//! Only the header inside the block managed by MakeWiz is read.
+
+use crate::output;
+
+const VERSION_PREFIX: &str = "# Generated by MakeWiz ";
+const INVOCATION_PREFIX: &str = "# Invocation: makewiz";
+
+/// Generates the provenance header for a Makefile.
+///
+/// # Arguments
+///
+/// * `args` - The arguments the Makefile was generated with, without the program name.
+///
+/// # Returns
+///
+/// A `String` containing the header, followed by an empty line.
+pub fn generate_header(args: &[String]) -> String {
+    let mut invocation = String::from(INVOCATION_PREFIX);
+    for arg in args {
+        invocation.push(' ');
+        invocation.push_str(&quote(arg));
+    }
+
+    format!("{}{}\n{}\n\n", VERSION_PREFIX, env!("CARGO_PKG_VERSION"), invocation)
+}
+
+/// Reads the recorded arguments from the provenance header in the managed block of a Makefile.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the Makefile.
+///
+/// # Returns
+///
+/// An `Option` containing the recorded arguments, without the program name,
+/// or `None` if the managed block of the Makefile has no valid header.
+pub fn parse_invocation(contents: &str) -> Option<Vec<String>> {
+    let block = output::get_managed_block(contents)?;
+    let line = block.lines().find(|line| line.starts_with(INVOCATION_PREFIX))?;
+    let rest = &line[INVOCATION_PREFIX.len()..];
+
+    // Guards against matching a longer program name, like "makewizard"
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+
+    split_words(rest)
+}
+
+/// Removes the line with the MakeWiz version from the Makefile contents, so Makefiles generated
+/// by different versions of MakeWiz can be compared.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the Makefile.
+///
+/// # Returns
+///
+/// A `String` containing the Makefile without the version line.
+pub fn strip_version(contents: &str) -> String {
+    contents.split_inclusive('\n')
+        .filter(|line| !line.starts_with(VERSION_PREFIX))
+        .collect()
+}
+
+/// Quotes an argument the way a POSIX shell would need it.
+fn quote(arg: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "_-./=:,+@%".contains(c);
+
+    if !arg.is_empty() && arg.chars().all(is_safe) {
+        arg.to_string()
+    }
+    else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Splits a line into words, following the quoting rules of a POSIX shell.
+fn split_words(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' => {
+                if let Some(word) = word.take() {
+                    words.push(word);
+                }
+            }
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => word.push(chars.next()?),
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => word.get_or_insert_with(String::new).push(chars.next()?),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    if let Some(word) = word {
+        words.push(word);
+    }
+
+    Some(words)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn header_round_trip() {
+        let args = vec![String::from("-c"), String::from("zig cc"), String::from("-e"),
+            String::from("it's"), String::from("")];
+
+        let header = generate_header(&args);
+
+        assert_eq!(format!("# Generated by MakeWiz {}\n# Invocation: makewiz -c 'zig cc' -e 'it'\\''s' ''\n\n",
+            env!("CARGO_PKG_VERSION")), header);
+        assert_eq!(Some(args), parse_invocation(&output::wrap_in_markers(&header)));
+    }
+
+    #[test]
+    fn parsing_invocation_without_arguments() {
+        assert_eq!(Some(Vec::new()), parse_invocation("# BEGIN MAKEWIZ\n# Invocation: makewiz\n# END MAKEWIZ\n"));
+    }
+
+    #[test]
+    fn parsing_invocation_outside_of_managed_block() {
+        assert_eq!(None, parse_invocation("# Invocation: makewiz -m\n"));
+        assert_eq!(Some(vec![String::from("-t")]),
+            parse_invocation("# Invocation: makewiz -m\n# BEGIN MAKEWIZ\n# Invocation: makewiz -t\n# END MAKEWIZ\n"));
+    }
+
+    #[test]
+    fn stripping_version() {
+        assert_eq!("# BEGIN MAKEWIZ\n# Invocation: makewiz\n", strip_version("# BEGIN MAKEWIZ\n# Generated by MakeWiz 0.1.0\n# Invocation: makewiz\n"));
+    }
+
+    #[test]
+    fn parsing_invalid_invocation() {
+        assert_eq!(None, parse_invocation("all: main\n"));
+        assert_eq!(None, parse_invocation("# BEGIN MAKEWIZ\n# Invocation: makewizard\n# END MAKEWIZ\n"));
+        assert_eq!(None, parse_invocation("# BEGIN MAKEWIZ\n# Invocation: makewiz -c 'unterminated\n# END MAKEWIZ\n"));
+    }
+}
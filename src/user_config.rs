@@ -1,25 +1,51 @@
 //! Manages the user config file.
 //!
 //! The user config file, stored in TOML format, holds default values for the C/C++ compiler
-//! and executable name. Users can set these values to avoid specifying them every time they
-//! use MakeWiz. If no custom defaults are chosen, they will be set to:
+//! and executable name, as well as defaults for Java projects. Users can set these values to
+//! avoid specifying them every time they use MakeWiz. If no custom defaults are chosen, they will be set to:
 //!
 //! - Compiler: g++
+//! - Compiler preset: none (the compiler name is used as is)
 //! - Executable name: main
+//! - Java classpath: empty
+//! - JUnit console launcher JAR: lib/junit-platform-console-standalone.jar
+//! - Java release, lint profile, source encoding and additional javac flags: empty (javac defaults)
+//! - Java resources directory: empty (resources/ is used if it exists)
+//! - Java doc and sources-jar targets: disabled
+//! - Custom extension mappings: none (only the built-in extensions are recognized)
 //!
 //! This module provides functions for reading, updating, and printing the user configuration.
 
 use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{Write, Read};
 use std::path::Path;
 
+use crate::build_data::ExtensionRole;
+use crate::compiler_preset::CompilerPreset;
+use crate::java_data;
+
 /// Represents an attribute that can be updated in the user config file.
 ///
 /// These attributes mirror the field names in the config file.
 pub enum Attribute {
     CompilerName(String),
+    /// Sets the compiler preset, or removes it if the preset is `None`.
+    CompilerPreset(Option<CompilerPreset>),
     ExecutableName(String),
+    JavaClasspath(Vec<String>),
+    JunitJar(String),
+    JavaRelease(String),
+    JavaLint(String),
+    JavaWerror(bool),
+    JavaEncoding(String),
+    JavacFlags(String),
+    JavaResources(String),
+    JavaDoc(bool),
+    JavaSourcesJar(bool),
+    /// Maps an extension to a role, or removes its mapping if the role is `None`.
+    Extension(String, Option<ExtensionRole>),
 }
 
 /// Represents the user config file.
@@ -30,6 +56,58 @@ pub enum Attribute {
 pub struct UserConfig {
     pub compiler_name: String,
     pub executable_name: String,
+
+    /// The compiler driver preset, used instead of the compiler name if set.
+    #[serde(default)]
+    pub compiler_preset: Option<CompilerPreset>,
+
+    /// Classpath entries (JARs and directories) added to every Java Makefile.
+    #[serde(default)]
+    pub java_classpath: Vec<String>,
+
+    /// The JUnit console launcher JAR used by the Java `test` target.
+    #[serde(default = "default_junit_jar")]
+    pub junit_jar: String,
+
+    /// The Java release passed to `javac --release`, e.g. `17`.
+    #[serde(default)]
+    pub java_release: String,
+
+    /// The lint profile passed to `javac -Xlint`, e.g. `all`.
+    #[serde(default)]
+    pub java_lint: String,
+
+    /// Whether `javac` should treat warnings as errors.
+    #[serde(default)]
+    pub java_werror: bool,
+
+    /// The source file encoding passed to `javac -encoding`, e.g. `UTF-8`.
+    #[serde(default)]
+    pub java_encoding: String,
+
+    /// Additional flags passed to `javac`.
+    #[serde(default)]
+    pub javac_flags: String,
+
+    /// The directory with resources copied into the Java output directory.
+    #[serde(default)]
+    pub java_resources: String,
+
+    /// Whether Java Makefiles get a `doc` target.
+    #[serde(default)]
+    pub java_doc: bool,
+
+    /// Whether Java Makefiles get a `sources-jar` target.
+    #[serde(default)]
+    pub java_sources_jar: bool,
+
+    /// Custom extension mappings, taking precedence over the built-in extensions.
+    #[serde(default)]
+    pub extensions: BTreeMap<String, ExtensionRole>,
+}
+
+fn default_junit_jar() -> String {
+    String::from(java_data::JUNIT_JAR)
 }
 
 impl UserConfig {
@@ -55,15 +133,8 @@ impl UserConfig {
         config
     }
 
-    fn default() -> Self {
-        Self {
-            compiler_name: String::from("g++"),
-            executable_name: String::from("main"),
-        }
-    }
-
     /// Updates the config file with the provided attribute.
-    /// This can be used to change the default compiler name or executable name.
+    /// This can be used to change the default compiler name, executable name or Java options.
     ///
     /// # Arguments
     ///
@@ -74,7 +145,20 @@ impl UserConfig {
 
         match attribute {
             Attribute::CompilerName(name) => { config.compiler_name = name; }
+            Attribute::CompilerPreset(preset) => { config.compiler_preset = preset; }
             Attribute::ExecutableName(name) => { config.executable_name = name; }
+            Attribute::JavaClasspath(entries) => { config.java_classpath = entries; }
+            Attribute::JunitJar(path) => { config.junit_jar = path; }
+            Attribute::JavaRelease(release) => { config.java_release = release; }
+            Attribute::JavaLint(profile) => { config.java_lint = profile; }
+            Attribute::JavaWerror(werror) => { config.java_werror = werror; }
+            Attribute::JavaEncoding(encoding) => { config.java_encoding = encoding; }
+            Attribute::JavacFlags(flags) => { config.javac_flags = flags; }
+            Attribute::JavaResources(dir) => { config.java_resources = dir; }
+            Attribute::JavaDoc(doc) => { config.java_doc = doc; }
+            Attribute::JavaSourcesJar(sources_jar) => { config.java_sources_jar = sources_jar; }
+            Attribute::Extension(extension, Some(role)) => { config.extensions.insert(extension, role); }
+            Attribute::Extension(extension, None) => { config.extensions.remove(&extension); }
         }
 
         UserConfig::create_config_file(config, config_path);
@@ -96,7 +180,44 @@ impl UserConfig {
         let config = UserConfig::get_current_config(config_path);
 
         println!("Default compiler name: {}", config.compiler_name);
+        println!("Default compiler preset: {}", config.compiler_preset.map_or(String::new(), |preset| format!("{:?}", preset).to_lowercase()));
         println!("Default executable name: {}", config.executable_name);
+        println!("Default Java classpath: {}", config.java_classpath.join(":"));
+        println!("Default JUnit console launcher JAR: {}", config.junit_jar);
+        println!("Default Java release: {}", config.java_release);
+        println!("Default Java lint profile: {}", config.java_lint);
+        println!("Default Java warnings as errors: {}", config.java_werror);
+        println!("Default Java source encoding: {}", config.java_encoding);
+        println!("Default additional javac flags: {}", config.javac_flags);
+        println!("Default Java resources directory: {}", config.java_resources);
+        println!("Default Java doc target: {}", config.java_doc);
+        println!("Default Java sources-jar target: {}", config.java_sources_jar);
+
+        let extensions: Vec<String> = config.extensions.iter()
+            .map(|(extension, role)| format!("{}={}", extension, format!("{:?}", role).to_lowercase()))
+            .collect();
+        println!("Custom extensions: {}", extensions.join(" "));
+    }
+}
+
+impl Default for UserConfig {
+    fn default() -> Self {
+        Self {
+            compiler_name: String::from("g++"),
+            executable_name: String::from("main"),
+            compiler_preset: None,
+            java_classpath: Vec::new(),
+            junit_jar: default_junit_jar(),
+            java_release: String::new(),
+            java_lint: String::new(),
+            java_werror: false,
+            java_encoding: String::new(),
+            javac_flags: String::new(),
+            java_resources: String::new(),
+            java_doc: false,
+            java_sources_jar: false,
+            extensions: BTreeMap::new(),
+        }
     }
 }
 
@@ -112,6 +233,7 @@ mod test {
         let expected = UserConfig {
             compiler_name: String::from("compiler name to get"),
             executable_name: String::from("executable name to get"),
+            ..UserConfig::default()
         };
 
         assert_eq!(expected, UserConfig::get_current_config(config_path));
@@ -135,12 +257,14 @@ mod test {
         let created_config = UserConfig {
             compiler_name: String::from("created compiler name"),
             executable_name: String::from("created executable name"),
+            ..UserConfig::default()
         };
         UserConfig::create_config_file(created_config, config_path);
 
         let expected = UserConfig {
             compiler_name: String::from("created compiler name"),
             executable_name: String::from("created executable name"),
+            ..UserConfig::default()
         };
 
         assert_eq!(expected, UserConfig::get_current_config(config_path));
@@ -153,6 +277,7 @@ mod test {
         let created_config = UserConfig {
             compiler_name: String::from("created compiler name"),
             executable_name: String::from("created executable name"),
+            ..UserConfig::default()
         };
         UserConfig::create_config_file(created_config, config_path);
 
@@ -161,6 +286,7 @@ mod test {
         let expected = UserConfig {
             compiler_name: String::from("new compiler name"),
             executable_name: String::from("created executable name"),
+            ..UserConfig::default()
         };
 
         assert_eq!(expected, UserConfig::get_current_config(config_path));
@@ -170,8 +296,87 @@ mod test {
         let expected = UserConfig {
             compiler_name: String::from("new compiler name"),
             executable_name: String::from("new executable name"),
+            ..UserConfig::default()
+        };
+
+        assert_eq!(expected, UserConfig::get_current_config(config_path));
+
+        UserConfig::update_config(Attribute::JavaClasspath(vec![String::from("lib/a.jar"), String::from("classes")]), config_path);
+
+        let expected = UserConfig {
+            compiler_name: String::from("new compiler name"),
+            executable_name: String::from("new executable name"),
+            java_classpath: vec![String::from("lib/a.jar"), String::from("classes")],
+            ..UserConfig::default()
+        };
+
+        assert_eq!(expected, UserConfig::get_current_config(config_path));
+
+        UserConfig::update_config(Attribute::JunitJar(String::from("tools/junit.jar")), config_path);
+
+        let expected = UserConfig {
+            compiler_name: String::from("new compiler name"),
+            executable_name: String::from("new executable name"),
+            java_classpath: vec![String::from("lib/a.jar"), String::from("classes")],
+            junit_jar: String::from("tools/junit.jar"),
+            ..UserConfig::default()
+        };
+
+        assert_eq!(expected, UserConfig::get_current_config(config_path));
+
+        UserConfig::update_config(Attribute::JavaRelease(String::from("17")), config_path);
+        UserConfig::update_config(Attribute::JavaLint(String::from("all")), config_path);
+        UserConfig::update_config(Attribute::JavaWerror(true), config_path);
+        UserConfig::update_config(Attribute::JavaEncoding(String::from("UTF-8")), config_path);
+        UserConfig::update_config(Attribute::JavacFlags(String::from("-parameters")), config_path);
+        UserConfig::update_config(Attribute::JavaResources(String::from("assets")), config_path);
+        UserConfig::update_config(Attribute::JavaDoc(true), config_path);
+        UserConfig::update_config(Attribute::JavaSourcesJar(true), config_path);
+
+        let expected = UserConfig {
+            compiler_name: String::from("new compiler name"),
+            executable_name: String::from("new executable name"),
+            java_classpath: vec![String::from("lib/a.jar"), String::from("classes")],
+            junit_jar: String::from("tools/junit.jar"),
+            java_release: String::from("17"),
+            java_lint: String::from("all"),
+            java_werror: true,
+            java_encoding: String::from("UTF-8"),
+            javac_flags: String::from("-parameters"),
+            java_resources: String::from("assets"),
+            java_doc: true,
+            java_sources_jar: true,
+            ..UserConfig::default()
+        };
+
+        assert_eq!(expected, UserConfig::get_current_config(config_path));
+
+        UserConfig::update_config(Attribute::Extension(String::from("c++"), Some(ExtensionRole::Cpp)), config_path);
+        UserConfig::update_config(Attribute::Extension(String::from("inc"), Some(ExtensionRole::Header)), config_path);
+        UserConfig::update_config(Attribute::Extension(String::from("inc"), None), config_path);
+        UserConfig::update_config(Attribute::CompilerPreset(Some(CompilerPreset::Zig)), config_path);
+
+        let expected = UserConfig {
+            compiler_name: String::from("new compiler name"),
+            executable_name: String::from("new executable name"),
+            java_classpath: vec![String::from("lib/a.jar"), String::from("classes")],
+            junit_jar: String::from("tools/junit.jar"),
+            java_release: String::from("17"),
+            java_lint: String::from("all"),
+            java_werror: true,
+            java_encoding: String::from("UTF-8"),
+            javac_flags: String::from("-parameters"),
+            java_resources: String::from("assets"),
+            java_doc: true,
+            java_sources_jar: true,
+            extensions: BTreeMap::from([(String::from("c++"), ExtensionRole::Cpp)]),
+            compiler_preset: Some(CompilerPreset::Zig),
         };
 
         assert_eq!(expected, UserConfig::get_current_config(config_path));
+
+        UserConfig::update_config(Attribute::CompilerPreset(None), config_path);
+
+        assert_eq!(None, UserConfig::get_current_config(config_path).compiler_preset);
     }
 }
@@ -0,0 +1,7 @@
+    .text
+    .globl add
+    .type add, @function
+add:
+    leaq (%rdi,%rsi), %rax
+    ret
+    .section .note.GNU-stack,"",@progbits
@@ -0,0 +1,11 @@
+#include <stdio.h>
+
+#include "answer.h"
+
+long add(long a, long b);
+long get_answer(void);
+
+int main(void) {
+    printf("%ld %ld\n", add(2, 3), get_answer());
+    return add(2, 3) == 5 && get_answer() == ANSWER ? 0 : 1;
+}
@@ -0,0 +1,11 @@
+#include <stdio.h>
+#include "calc.h"
+#include "parser.tab.h"
+
+void yyerror(const char *message) {
+    fprintf(stderr, "Error: %s\n", message);
+}
+
+int main(void) {
+    return yyparse();
+}
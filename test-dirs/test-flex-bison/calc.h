@@ -0,0 +1,7 @@
+#ifndef CALC_H
+#define CALC_H
+
+int yylex(void);
+void yyerror(const char *message);
+
+#endif
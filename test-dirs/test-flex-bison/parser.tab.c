@@ -0,0 +1 @@
+/* Generated by a previous build */
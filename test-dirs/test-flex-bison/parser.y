@@ -0,0 +1,24 @@
+%{
+#include <stdio.h>
+#include "calc.h"
+%}
+
+%token NUMBER
+%left '+' '-'
+%left '*' '/'
+
+%%
+lines
+    : /* empty */
+    | lines expr '\n'   { printf("%d\n", $2); }
+    ;
+
+expr
+    : NUMBER
+    | expr '+' expr     { $$ = $1 + $3; }
+    | expr '-' expr     { $$ = $1 - $3; }
+    | expr '*' expr     { $$ = $1 * $3; }
+    | expr '/' expr     { $$ = $1 / $3; }
+    | '(' expr ')'      { $$ = $2; }
+    ;
+%%
@@ -0,0 +1,10 @@
+module geometry
+  use constants, only: pi
+  implicit none
+contains
+  function circle_area(radius) result(area)
+    real, intent(in) :: radius
+    real :: area
+    area = pi * radius * radius
+  end function circle_area
+end module geometry
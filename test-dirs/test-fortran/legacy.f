@@ -0,0 +1,4 @@
+C     Fixed-form helper, called from main
+      subroutine legacy_hello()
+      print *, 'Hello from fixed-form Fortran'
+      end
@@ -0,0 +1,5 @@
+#include <stdio.h>
+
+void report(float value) {
+    printf("Area: %.2f\n", value);
+}
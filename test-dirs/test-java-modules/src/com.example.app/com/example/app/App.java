@@ -0,0 +1,9 @@
+package com.example.app;
+
+import com.example.greeter.Greeter;
+
+public class App {
+    public static void main(String[] args) {
+        System.out.println(Greeter.greet("modules"));
+    }
+}
@@ -0,0 +1,7 @@
+/**
+ * The application module.
+ */
+module com.example.app {
+    requires com.example.greeter;
+    requires java.logging;
+}
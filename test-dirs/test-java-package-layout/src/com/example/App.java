@@ -0,0 +1,9 @@
+package com.example;
+
+import com.example.util.Helper;
+
+public class App {
+    public static void main(String[] args) {
+        System.out.println(Helper.greeting());
+    }
+}
@@ -0,0 +1,7 @@
+package com.example.util;
+
+public class Helper {
+    public static String greeting() {
+        return "Hello from a package!";
+    }
+}
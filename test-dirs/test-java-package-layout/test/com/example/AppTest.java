@@ -0,0 +1,14 @@
+package com.example;
+
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import com.example.util.Helper;
+import org.junit.jupiter.api.Test;
+
+class AppTest {
+    @Test
+    void greetingIsFriendly() {
+        assertEquals("Hello from a package!", TestUtils.expectedGreeting());
+        assertEquals(TestUtils.expectedGreeting(), Helper.greeting());
+    }
+}
@@ -0,0 +1,7 @@
+package com.example;
+
+class TestUtils {
+    static String expectedGreeting() {
+        return "Hello from a package!";
+    }
+}
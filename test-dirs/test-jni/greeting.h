@@ -0,0 +1,6 @@
+#ifndef GREETING_H
+#define GREETING_H
+
+#define GREETING_SIZE 64
+
+#endif
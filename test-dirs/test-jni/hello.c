@@ -0,0 +1,14 @@
+#include <stdio.h>
+
+#include "com_example_NativeHello.h"
+#include "greeting.h"
+
+JNIEXPORT jstring JNICALL Java_com_example_NativeHello_greet(JNIEnv *env, jclass cls, jstring name) {
+    const char *chars = (*env)->GetStringUTFChars(env, name, NULL);
+    char message[GREETING_SIZE];
+
+    snprintf(message, sizeof(message), "Hello from C, %s!", chars);
+    (*env)->ReleaseStringUTFChars(env, name, chars);
+
+    return (*env)->NewStringUTF(env, message);
+}
@@ -0,0 +1,13 @@
+package com.example;
+
+public class NativeHello {
+    static {
+        System.loadLibrary("hello");
+    }
+
+    private static native String greet(String name);
+
+    public static void main(String[] args) {
+        System.out.println(greet("JNI"));
+    }
+}
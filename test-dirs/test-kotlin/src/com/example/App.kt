@@ -0,0 +1,6 @@
+package com.example
+
+fun main(args: Array<String>) {
+    val greeter = Greeter("Kotlin")
+    println(greeter.greet())
+}
@@ -0,0 +1,15 @@
+package com.example;
+
+import com.example.util.StringsKt;
+
+public class Greeter {
+    private final String name;
+
+    public Greeter(String name) {
+        this.name = name;
+    }
+
+    public String greet() {
+        return StringsKt.shout("Hello, " + name);
+    }
+}
@@ -0,0 +1,8 @@
+package com.example.util
+
+/* Top-level functions land in StringsKt */
+fun shout(text: String): String = text.uppercase() + "!"
+
+object Formatter {
+    fun main(args: Array<String>) { }
+}
@@ -0,0 +1,7 @@
+package helper;
+
+public class Helper {
+    public static void main(String[] args) {
+        System.out.println("helper");
+    }
+}
@@ -0,0 +1,8 @@
+#include <iostream>
+
+#include "tool.hpp"
+
+int main() {
+    std::cout << toolName() << std::endl;
+    return 0;
+}
@@ -0,0 +1,8 @@
+#ifndef TOOL_HPP
+#define TOOL_HPP
+
+inline const char *toolName() {
+    return "tool";
+}
+
+#endif
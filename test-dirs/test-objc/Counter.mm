@@ -0,0 +1,10 @@
+#import "Greeter.h"
+#include <vector>
+
+int countGreetings(Greeter *greeter) {
+    std::vector<NSString *> names = {@"Ada", @"Brian"};
+    for (NSString *name : names) {
+        [greeter greet:name];
+    }
+    return static_cast<int>(names.size());
+}
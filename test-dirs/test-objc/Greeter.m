@@ -0,0 +1,7 @@
+#import "Greeter.h"
+
+@implementation Greeter
+- (void)greet:(NSString *)name {
+    NSLog(@"Hello, %@!", name);
+}
+@end
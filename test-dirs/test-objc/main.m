@@ -0,0 +1,11 @@
+#import "Greeter.h"
+
+int countGreetings(Greeter *greeter);
+
+int main(void) {
+    @autoreleasepool {
+        Greeter *greeter = [[Greeter alloc] init];
+        countGreetings(greeter);
+    }
+    return 0;
+}
@@ -0,0 +1,11 @@
+#include <iostream>
+#include "person.pb.h"
+
+int main() {
+    people::Person person;
+    person.set_name("Ada");
+    person.set_id(1);
+
+    std::cout << person.DebugString();
+    return 0;
+}
@@ -0,0 +1,9 @@
+package com.example
+
+/* The entry point object doesn't have to be named after the file */
+object Launcher {
+  def main(args: Array[String]): Unit = {
+    val shapes = List(Circle(1.0), Square(2.0))
+    shapes.foreach(shape => println(f"${shape.name}: ${shape.area}%.2f"))
+  }
+}
@@ -0,0 +1,16 @@
+package com.example
+
+sealed trait Shape {
+  def name: String
+  def area: Double
+}
+
+case class Circle(radius: Double) extends Shape {
+  def name: String = "circle"
+  def area: Double = math.Pi * radius * radius
+}
+
+case class Square(side: Double) extends Shape {
+  def name: String = "square"
+  def area: Double = side * side
+}
@@ -1,12 +1,34 @@
 use makewiz::cli;
 use makewiz::build_data;
+use makewiz::compiler_preset;
+use makewiz::java_data;
+use makewiz::user_config;
 
 mod test {
     use super::*;
 
     use std::fs;
+    use std::path::Path;
+    use std::process::Command;
     use clap::Parser;
 
+    /// Copies a fixture directory, so the generated build runs outside of the repository.
+    fn copy_dir(from: &Path, to: &Path) {
+        fs::create_dir_all(to).unwrap();
+
+        for entry in fs::read_dir(from).unwrap() {
+            let path = entry.unwrap().path();
+            let target = to.join(path.file_name().unwrap());
+
+            if path.is_dir() {
+                copy_dir(&path, &target);
+            }
+            else {
+                fs::copy(&path, &target).unwrap();
+            }
+        }
+    }
+
     #[test]
     fn makefile_creation() {
         let paths_to_files = fs::read_dir("./test-dirs/test-makefile-creation").unwrap();
@@ -64,30 +86,931 @@ clean:
     }
 
     #[test]
-    fn java_makefile_creation() {
-        let paths_to_files = fs::read_dir("./test-dirs/test-java-makefile-creation").unwrap();
+    fn writing_to_missing_output_directory() {
+        let project_dir = std::env::temp_dir().join(format!("makewiz-output-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&project_dir);
+        copy_dir(Path::new("./test-dirs/test-makefile-creation"), &project_dir);
+
+        let output = Command::new(env!("CARGO_BIN_EXE_makewiz"))
+            .args(["--output", "missing/Makefile"])
+            .env("XDG_CONFIG_HOME", &project_dir)
+            .current_dir(&project_dir)
+            .output()
+            .unwrap();
+        fs::remove_dir_all(&project_dir).unwrap();
+
+        assert_eq!(Some(1), output.status.code());
+        assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: unable to create missing/Makefile: "));
+    }
+
+    #[test]
+    fn recording_options_from_config() {
+        let project_dir = std::env::temp_dir().join(format!("makewiz-config-{}", std::process::id()));
+        let config_dir = project_dir.join("config");
+        let _ = fs::remove_dir_all(&project_dir);
+        copy_dir(Path::new("./test-dirs/test-makefile-creation"), &project_dir);
+
+        let makewiz = |args: &[&str], config_dir: &Path| {
+            let status = Command::new(env!("CARGO_BIN_EXE_makewiz"))
+                .args(args)
+                .env("XDG_CONFIG_HOME", config_dir)
+                .current_dir(&project_dir)
+                .output()
+                .unwrap()
+                .status;
+            status.code()
+        };
+
+        makewiz(&["set-compiler", "clang"], &config_dir);
+        makewiz(&["set-preset", "zig"], &config_dir);
+        makewiz(&["set-extension", "hpp", "ignore"], &config_dir);
+        assert_eq!(Some(0), makewiz(&["-e", "app"], &config_dir));
+        let makefile = fs::read_to_string(project_dir.join("Makefile")).unwrap();
+
+        // Checking and regenerating with another config gives the same Makefile
+        let other_config_dir = project_dir.join("other-config");
+        let check = makewiz(&["--check"], &other_config_dir);
+        assert_eq!(Some(0), makewiz(&["regen"], &other_config_dir));
+        let regenerated = fs::read_to_string(project_dir.join("Makefile")).unwrap();
+        fs::remove_dir_all(&project_dir).unwrap();
+
+        assert!(makefile.contains("# Invocation: makewiz -c clang --preset zig --extension hpp=ignore -e app\n"));
+        assert!(makefile.contains("CC = zig c++\n"));
+        assert!(!makefile.contains(".hpp"));
+        assert_eq!(Some(0), check);
+        assert_eq!(makefile, regenerated);
+    }
+
+    #[test]
+    fn extended_extensions_makefile_creation() {
+        let paths_to_files = fs::read_dir("./test-dirs/test-extracting-filenames/extended-extensions").unwrap();
+
+        let mut file_names = build_data::BuildData::extract_names(paths_to_files).unwrap();
+        file_names.compiler = String::from("g++");
+        file_names.executable = String::from("main");
+
+        let makefile = makewiz::generate_makefile(&file_names);
+
+        assert!(makefile.contains("\
+# Source files and object files
+OBJS = Legacy.o main.o matrix.o vector.o
+SOURCE = Legacy.C main.c matrix.cc vector.cxx
+HEADER = matrix.hh matrix.tpp vector.hxx vector.inl
+"));
+        assert!(makefile.contains("\
+# Compiler and flags
+CC = gcc
+CXX = g++
+"));
+        assert!(makefile.contains("\
+# Linking rules
+$(OUT): $(OBJS)
+\t$(CXX) -g $(OBJS) -o $(OUT) $(LFLAGS) $(LDLIBS)
+
+# Compilation rules
+%.o: %.C $(HEADER)
+\t$(CXX) $(FLAGS) -o $@ $<
+%.o: %.c $(HEADER)
+\t$(CC) $(FLAGS) -o $@ $<
+%.o: %.cc $(HEADER)
+\t$(CXX) $(FLAGS) -o $@ $<
+%.o: %.cxx $(HEADER)
+\t$(CXX) $(FLAGS) -o $@ $<
+"));
+    }
+
+    #[test]
+    fn assembly_makefile_creation() {
+        let mut file_names = build_data::BuildData::extract_names(fs::read_dir("./test-dirs/test-assembly").unwrap()).unwrap();
+        file_names.compiler = user_config::UserConfig::default().compiler_name;
+        file_names.executable = String::from("main");
+
+        let expected = "\
+# Compiler and flags
+CC = gcc
+FLAGS = -g -c -Wall
+LFLAGS = 
+AS = as
+ASFLAGS = -g
+
+# Source files and object files
+OBJS = add.o answer.o main.o
+SOURCE = add.s answer.S main.c
+HEADER = answer.h
+OUT = main
+
+# Libraries
+LDLIBS = 
+
+# Default target
+all: $(OUT)
+
+# Linking rules
+$(OUT): $(OBJS)
+\t$(CC) -g $(OBJS) -o $(OUT) $(LFLAGS) $(LDLIBS)
+
+# Compilation rules
+%.o: %.S $(HEADER)
+\t$(CC) $(FLAGS) -o $@ $<
+%.o: %.c $(HEADER)
+\t$(CC) $(FLAGS) -o $@ $<
+%.o: %.s
+\t$(AS) $(ASFLAGS) -o $@ $<
+
+# Clean rule
+clean:
+\trm -f $(OBJS) $(OUT)\n";
+        assert_eq!(expected, makewiz::generate_makefile(&file_names));
+    }
+
+    #[test]
+    fn fortran_makefile_creation() {
+        let mut file_names = build_data::BuildData::extract_names(fs::read_dir("./test-dirs/test-fortran").unwrap()).unwrap();
+        file_names.compiler = String::from("gcc");
+        file_names.executable = String::from("main");
+
+        let expected = "\
+# Compiler and flags
+CC = gcc
+FLAGS = -g -c -Wall
+LFLAGS = 
+FC = gfortran
+FFLAGS = -g -Wall
+
+# Source files and object files
+OBJS = constants.o geometry.o legacy.o main.o report.o
+SOURCE = constants.f90 geometry.f90 legacy.f main.F90 report.c
+HEADER = 
+MODS = constants.mod geometry.mod
+OUT = main
+
+# Libraries
+LDLIBS = 
+
+# Default target
+all: $(OUT)
+
+# Linking rules
+$(OUT): $(OBJS)
+\t$(FC) -g $(OBJS) -o $(OUT) $(LFLAGS) $(LDLIBS)
+
+# Compilation rules
+%.o: %.F90
+\t$(FC) $(FFLAGS) -c -o $@ $<
+%.o: %.c $(HEADER)
+\t$(CC) $(FLAGS) -o $@ $<
+%.o: %.f
+\t$(FC) $(FFLAGS) -c -o $@ $<
+%.o: %.f90
+\t$(FC) $(FFLAGS) -c -o $@ $<
+
+# Fortran module dependencies
+geometry.o: constants.o
+main.o: geometry.o
+
+# Clean rule
+clean:
+\trm -f $(OBJS) $(OUT) $(MODS)\n";
+        assert_eq!(expected, makewiz::generate_makefile(&file_names));
+    }
+
+    #[test]
+    fn objc_makefile_creation() {
+        let mut file_names = build_data::BuildData::extract_names(fs::read_dir("./test-dirs/test-objc").unwrap()).unwrap();
+        file_names.compiler = String::from("g++");
+        file_names.executable = String::from("main");
+
+        let expected = "\
+# Compiler and flags
+CC = g++
+FLAGS = -g -c -Wall
+LFLAGS = 
+OBJCFLAGS = -g -c -Wall
+
+# Source files and object files
+OBJS = Counter.o Greeter.o main.o
+SOURCE = Counter.mm Greeter.m main.m
+HEADER = Greeter.h
+OUT = main
+
+# Libraries
+LDLIBS = -lobjc
+
+# Default target
+all: $(OUT)
+
+# Linking rules
+$(OUT): $(OBJS)
+\t$(CC) -g $(OBJS) -o $(OUT) $(LFLAGS) $(LDLIBS)
+
+# Compilation rules
+%.o: %.m $(HEADER)
+\t$(CC) $(OBJCFLAGS) -o $@ $<
+%.o: %.mm $(HEADER)
+\t$(CC) $(OBJCFLAGS) -o $@ $<
+
+# Clean rule
+clean:
+\trm -f $(OBJS) $(OUT)\n";
+        assert_eq!(expected, makewiz::generate_makefile(&file_names));
+
+        file_names.objc_runtime = build_data::ObjcRuntime::Gnustep;
+        let makefile = makewiz::generate_makefile(&file_names);
+
+        assert!(makefile.contains("OBJCFLAGS = -g -c -Wall $(shell gnustep-config --objc-flags)\n"));
+        assert!(makefile.contains("LDLIBS = $(shell gnustep-config --base-libs)\n"));
+    }
+
+    #[test]
+    fn flex_bison_makefile_creation() {
+        let mut file_names = build_data::BuildData::extract_names(fs::read_dir("./test-dirs/test-flex-bison").unwrap()).unwrap();
+        file_names.compiler = String::from("gcc");
+        file_names.executable = String::from("calc");
+
+        let expected = "\
+# Compiler and flags
+CC = gcc
+FLAGS = -g -c -Wall
+LFLAGS = 
+LEX = flex
+YACC = bison
+YFLAGS = -d
+
+# Source files and object files
+OBJS = calc.o lexer.yy.o parser.tab.o
+SOURCE = calc.c lexer.l parser.y
+HEADER = calc.h parser.tab.h
+GENERATED = lexer.yy.c parser.tab.c parser.tab.h
+OUT = calc
+
+# Libraries
+LDLIBS = 
+
+# Default target
+all: $(OUT)
+
+# Linking rules
+$(OUT): $(OBJS)
+\t$(CC) -g $(OBJS) -o $(OUT) $(LFLAGS) $(LDLIBS)
+
+# Compilation rules
+%.o: %.c $(HEADER)
+\t$(CC) $(FLAGS) -o $@ $<
+%.yy.c: %.l
+\t$(LEX) -o $@ $<
+%.tab.c %.tab.h: %.y
+\t$(YACC) $(YFLAGS) -o $*.tab.c $<
+
+# Keep the generated sources
+.SECONDARY: $(GENERATED)
+
+# Clean rule
+clean:
+\trm -f $(OBJS) $(OUT) $(GENERATED)\n";
+        assert_eq!(expected, makewiz::generate_makefile(&file_names));
+    }
+
+    #[test]
+    fn protobuf_makefile_creation() {
+        let mut file_names = build_data::BuildData::extract_names(fs::read_dir("./test-dirs/test-protobuf").unwrap()).unwrap();
+        file_names.compiler = String::from("g++");
+        file_names.executable = String::from("main");
+
+        let expected = "\
+# Compiler and flags
+CC = g++
+FLAGS = -g -c -Wall $(PROTOBUF_CFLAGS)
+LFLAGS = 
+PROTOC = protoc
+PROTOBUF_CFLAGS := $(shell pkg-config --cflags protobuf 2>/dev/null)
+PROTOBUF_LIBS := $(shell pkg-config --libs protobuf 2>/dev/null || echo -lprotobuf)
+
+# Source files and object files
+OBJS = main.o person.pb.o
+SOURCE = main.cpp person.proto
+HEADER = person.pb.h
+GENERATED = person.pb.cc person.pb.h
+OUT = main
+
+# Libraries
+LDLIBS = $(PROTOBUF_LIBS)
+
+# Default target
+all: $(OUT)
+
+# Linking rules
+$(OUT): $(OBJS)
+\t$(CC) -g $(OBJS) -o $(OUT) $(LFLAGS) $(LDLIBS)
+
+# Compilation rules
+%.o: %.cc $(HEADER)
+\t$(CC) $(FLAGS) -o $@ $<
+%.o: %.cpp $(HEADER)
+\t$(CC) $(FLAGS) -o $@ $<
+%.pb.cc %.pb.h: %.proto
+\t$(PROTOC) --cpp_out=. $<
+
+# Keep the generated sources
+.SECONDARY: $(GENERATED)
+
+# Clean rule
+clean:
+\trm -f $(OBJS) $(OUT) $(GENERATED)\n";
+        assert_eq!(expected, makewiz::generate_makefile(&file_names));
+    }
+
+    #[test]
+    fn compiler_preset_makefile_creation() {
+        let mut file_names = build_data::BuildData::extract_names(fs::read_dir("./test-dirs/test-makefile-creation").unwrap()).unwrap();
+        file_names.compiler = String::from("g++");
+        file_names.executable = String::from("main");
+
+        let args = vec![String::from("target/debug/makewiz"), String::from("--preset"), String::from("zig"),
+            String::from("--target"), String::from("aarch64-linux-gnu"), String::from("--sanitize"),
+            String::from("address,undefined"), String::from("-m")];
+
+        let parsed_args = cli::CLI::parse_from(args);
+
+        file_names.driver = Some(compiler_preset::CompilerDriver {
+            preset: parsed_args.preset.unwrap(),
+            target: parsed_args.target.clone(),
+            sanitizers: parsed_args.sanitize.clone(),
+        });
+        let (lflags, ldlibs) = parsed_args.parse_flags();
+        file_names.lflags = lflags;
+        file_names.ldlibs = ldlibs;
+
+        let expected = "\
+# Compiler and flags
+CC = zig c++
+TARGET_FLAGS = -target aarch64-linux-gnu
+SANITIZE_FLAGS = -fsanitize=address,undefined
+DEPFLAGS = -MMD -MP
+DEPS = $(OBJS:.o=.d)
+FLAGS = -g -c -Wall $(TARGET_FLAGS) $(SANITIZE_FLAGS) $(DEPFLAGS)
+LFLAGS = $(TARGET_FLAGS) $(SANITIZE_FLAGS) -lm
+
+# Source files and object files
+OBJS = AnotherClass.o SomeClass.o main.o
+SOURCE = AnotherClass.cpp SomeClass.cpp main.cpp
+HEADER = AnotherClass.hpp SomeClass.hpp SomeHeader.hpp
+OUT = main
+
+# Libraries
+LDLIBS = 
+
+# Default target
+all: $(OUT)
+
+# Linking rules
+$(OUT): $(OBJS)
+\t$(CC) -g $(OBJS) -o $(OUT) $(LFLAGS) $(LDLIBS)
+
+# Compilation rules
+%.o: %.cpp $(HEADER)
+\t$(CC) $(FLAGS) -o $@ $<
+
+# Header dependencies written by the compiler
+-include $(DEPS)
+
+# Clean rule
+clean:
+\trm -f $(OBJS) $(OUT) $(DEPS)\n";
+        assert_eq!(expected, makewiz::generate_makefile(&file_names));
+    }
 
-        let file_names = build_data::BuildData::extract_names(paths_to_files).unwrap();
+    #[test]
+    fn java_makefile_creation() {
+        let java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-java-makefile-creation")).unwrap();
 
         let expected = "\
 # Compiler and flags
 JC = javac
 JFLAGS = -g
+JAR = jar
+JAVA = java
+
+# Directories
+SRC_DIR = .
+OUT_DIR = out
 
 # Source files and compiled classes
 SOURCE = FirstClass.java SecondClass.java
-CLASSES = FirstClass.class SecondClass.class
+CLASSES_STAMP = classes.stamp
+SOURCES_LIST = sources.txt
+
+# Default target
+default: $(CLASSES_STAMP)
+
+# List of source files passed to javac
+$(SOURCES_LIST): $(SOURCE)
+\t@printf '%s\\n' $(SOURCE) > $(SOURCES_LIST)
+
+# Compilation rule, a single javac invocation compiles all classes
+$(CLASSES_STAMP): $(SOURCES_LIST)
+\t@mkdir -p $(OUT_DIR)
+\t$(JC) $(JFLAGS) -d $(OUT_DIR) -sourcepath $(SRC_DIR) @$(SOURCES_LIST)
+\t@touch $(CLASSES_STAMP)
+
+# Clean rule to remove generated .class files
+clean:
+\trm -rf $(OUT_DIR) $(CLASSES_STAMP) $(SOURCES_LIST)\n";
+        assert_eq!(expected, makewiz::generate_java_makefile(&java_data));
+    }
+
+    #[test]
+    fn java_package_makefile_creation() {
+        let java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-java-package-layout")).unwrap();
+        let makefile = makewiz::generate_java_makefile(&java_data);
+
+        assert!(makefile.contains("SRC_DIR = src\n"));
+        assert!(makefile.contains("SOURCE = src/com/example/App.java src/com/example/util/Helper.java\n"));
+        assert!(!makefile.contains("CLASSES ="));
+        assert!(!makefile.contains("MAIN_CLASS"));
+    }
+
+    #[test]
+    fn java_resources_makefile_creation() {
+        let java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-java-package-layout")).unwrap();
+        let makefile = makewiz::generate_java_makefile(&java_data);
+
+        assert!(makefile.contains("\
+# Resources copied next to the compiled classes
+RESOURCE_DIR = resources
+RESOURCES = resources/app.properties resources/images/logo.txt
+RESOURCE_FILES = out/app.properties out/images/logo.txt
+"));
+        assert!(makefile.contains("\
+# Default target
+default: $(CLASSES_STAMP) $(RESOURCE_FILES)
+"));
+        assert!(makefile.contains("\
+# Copy the resources into the output directory
+$(OUT_DIR)/%: $(RESOURCE_DIR)/%
+\t@mkdir -p $(dir $@)
+\tcp $< $@
+"));
+    }
+
+    #[test]
+    fn java_jar_makefile_creation() {
+        let mut java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-java-package-layout")).unwrap();
+        java_data.main_class = Some(String::from("com.example.App"));
+        java_data.add_to_classpath(&[String::from("classes"), String::from("lib/dependency.jar")]);
+
+        let makefile = makewiz::generate_java_makefile(&java_data);
+
+        assert!(makefile.contains("\
+# Classpath with dependency JARs and directories
+CLASS_PATH = lib/dependency.jar:classes
+
+# Main class and executable jar
+MAIN_CLASS = com.example.App
+JAR_FILE = App.jar
+MANIFEST = manifest.txt
+MANIFEST_CLASS_PATH = lib/dependency.jar classes/
+"));
+        assert!(makefile.contains("\t$(JC) $(JFLAGS) -d $(OUT_DIR) -cp $(CLASS_PATH) -sourcepath $(SRC_DIR) @$(SOURCES_LIST)\n"));
+        assert!(makefile.contains("\
+# Executable jar with a generated manifest
+jar: $(JAR_FILE)
+
+$(MANIFEST): $(MAKEFILE_LIST)
+\t@echo \"Main-Class: $(MAIN_CLASS)\" > $(MANIFEST)
+\t@echo \"Class-Path: \" >> $(MANIFEST)
+\t@printf '  %s\\n' $(MANIFEST_CLASS_PATH) >> $(MANIFEST)
+
+$(JAR_FILE): $(CLASSES_STAMP) $(RESOURCE_FILES) $(MANIFEST)
+\t$(JAR) cfm $(JAR_FILE) $(MANIFEST) -C $(OUT_DIR) .
+
+# Run the main class
+run: $(CLASSES_STAMP) $(RESOURCE_FILES)
+\t$(JAVA) -cp $(OUT_DIR):$(CLASS_PATH) $(MAIN_CLASS)
+"));
+        assert!(makefile.ends_with("rm -rf $(OUT_DIR) $(CLASSES_STAMP) $(SOURCES_LIST) $(MANIFEST) $(JAR_FILE) $(TEST_OUT_DIR) $(TEST_CLASSES_STAMP) $(TEST_SOURCES_LIST)\n"));
+    }
+
+    #[test]
+    fn java_test_makefile_creation() {
+        let java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-java-package-layout")).unwrap();
+
+        let makefile = makewiz::generate_java_makefile(&java_data);
+
+        assert!(makefile.contains("\
+# Test sources and compiled test classes
+TEST_OUT_DIR = out-test
+TEST_SOURCE = test/com/example/AppTest.java test/com/example/TestUtils.java
+TEST_CLASSES_STAMP = test-classes.stamp
+TEST_SOURCES_LIST = test-sources.txt
+TEST_CLASS_NAMES = com.example.AppTest
+JUNIT_JAR = lib/junit-platform-console-standalone-1.10.0.jar
+"));
+        assert!(makefile.contains("\
+# List of test source files passed to javac
+$(TEST_SOURCES_LIST): $(TEST_SOURCE)
+\t@printf '%s\\n' $(TEST_SOURCE) > $(TEST_SOURCES_LIST)
+
+# Compilation rule for tests, against the main classes
+$(TEST_CLASSES_STAMP): $(CLASSES_STAMP) $(TEST_SOURCES_LIST)
+\t@mkdir -p $(TEST_OUT_DIR)
+\t$(JC) $(JFLAGS) -d $(TEST_OUT_DIR) -cp $(OUT_DIR):$(JUNIT_JAR):$(CLASS_PATH) @$(TEST_SOURCES_LIST)
+\t@touch $(TEST_CLASSES_STAMP)
+
+# Run the tests with the JUnit console launcher
+test: $(TEST_CLASSES_STAMP) $(RESOURCE_FILES)
+\t$(JAVA) -jar $(JUNIT_JAR) --class-path $(TEST_OUT_DIR):$(OUT_DIR):$(CLASS_PATH) $(addprefix --select-class=,$(TEST_CLASS_NAMES))
+"));
+    }
+
+    #[test]
+    fn java_compiler_options_makefile_creation() {
+        let mut java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-java-makefile-creation")).unwrap();
+        java_data.release = String::from("17");
+        java_data.lint = String::from("all,-serial");
+        java_data.werror = true;
+        java_data.encoding = String::from("UTF-8");
+        java_data.extra_flags = String::from("-parameters");
+
+        let makefile = makewiz::generate_java_makefile(&java_data);
+
+        assert!(makefile.starts_with("\
+# Compiler and flags
+JC = javac
+JFLAGS = -g $(JRELEASE) $(JLINT) $(JENCODING) $(JEXTRAFLAGS)
+JRELEASE = --release 17
+JLINT = -Xlint:all,-serial -Werror
+JENCODING = -encoding UTF-8
+JEXTRAFLAGS = -parameters
+JAR = jar
+JAVA = java
+"));
+    }
+
+    #[test]
+    fn java_module_makefile_creation() {
+        let mut java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-java-modules")).unwrap();
+        java_data.main_class = Some(String::from("com.example.app.App"));
+        java_data.add_to_classpath(&[String::from("lib/dependency.jar")]);
+
+        let makefile = makewiz::generate_java_makefile(&java_data);
+
+        assert!(makefile.contains("\
+# Directories
+MODULE_SOURCE_PATH = src
+OUT_DIR = out
+"));
+        assert!(makefile.contains("\
+# Modules compiled by javac
+MODULES = com.example.app,com.example.greeter
+
+# Module path with dependency JARs and directories
+MODULE_PATH = lib/dependency.jar
+
+# Main class and executable jar
+MAIN_CLASS = com.example.app.App
+MAIN_MODULE = com.example.app
+JAR_DIR = out-jars
+MODULE_JARS = out-jars/com.example.app.jar out-jars/com.example.greeter.jar
+"));
+        assert!(makefile.contains("\
+$(CLASSES_STAMP): $(SOURCE)
+\t@mkdir -p $(OUT_DIR)
+\t$(JC) $(JFLAGS) -d $(OUT_DIR) --module-path $(MODULE_PATH) $(foreach path,$(MODULE_SOURCE_PATH),--module-source-path $(path)) --module $(MODULES)
+\t@touch $(CLASSES_STAMP)
+"));
+        assert!(makefile.contains("\
+jar: $(MODULE_JARS)
+
+$(JAR_DIR)/%.jar: $(CLASSES_STAMP)
+\t@mkdir -p $(JAR_DIR)
+\t$(JAR) --create --file $@ -C $(OUT_DIR)/$* .
+
+# The jar of the main module launches the main class
+$(JAR_DIR)/$(MAIN_MODULE).jar: $(CLASSES_STAMP)
+\t@mkdir -p $(JAR_DIR)
+\t$(JAR) --create --file $@ --main-class $(MAIN_CLASS) -C $(OUT_DIR)/$(MAIN_MODULE) .
+
+# Run the main class from its module
+run: $(CLASSES_STAMP)
+\t$(JAVA) --module-path $(OUT_DIR):$(MODULE_PATH) --module $(MAIN_MODULE)/$(MAIN_CLASS)
+"));
+        assert!(!makefile.contains("SOURCES_LIST"));
+        assert!(!makefile.contains("MANIFEST"));
+        assert!(makefile.ends_with("rm -rf $(OUT_DIR) $(CLASSES_STAMP) $(JAR_DIR)\n"));
+    }
+
+    #[test]
+    fn java_module_sources_jar_makefile_creation() {
+        let mut java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-java-modules")).unwrap();
+        java_data.sources_jar = true;
+
+        let makefile = makewiz::generate_java_makefile(&java_data);
+
+        assert!(makefile.contains("SOURCES_JARS = com.example.app-sources.jar com.example.greeter-sources.jar\n"));
+        assert!(makefile.contains("\
+# Jars with the source files of every module, laid out by package
+sources-jar: $(SOURCES_JARS)
+
+com.example.app-sources.jar: $(SOURCE)
+\t$(JAR) cf $@ -C src/com.example.app com/example/app/App.java -C src/com.example.app module-info.java
+
+com.example.greeter-sources.jar: $(SOURCE)
+\t$(JAR) cf $@ -C src/com.example.greeter com/example/greeter/Greeter.java -C src/com.example.greeter module-info.java
+"));
+        assert!(makefile.ends_with("rm -rf $(OUT_DIR) $(CLASSES_STAMP) $(SOURCES_JARS)\n"));
+    }
+
+    #[test]
+    fn java_module_jars_contain_every_module() {
+        // Builds the jars with the real JDK tools, which may not be installed
+        let tools_available = ["make", "javac", "jar"].iter()
+            .all(|tool| Command::new(tool).arg("--version").output().is_ok());
+        if !tools_available {
+            eprintln!("Skipping: make, javac or jar isn't installed");
+            return;
+        }
+
+        let project_dir = std::env::temp_dir().join(format!("makewiz-modules-{}", std::process::id()));
+        copy_dir(Path::new("./test-dirs/test-java-modules"), &project_dir);
+
+        let mut java_data = java_data::JavaData::extract_sources(&project_dir).unwrap();
+        java_data.main_class = Some(String::from("com.example.app.App"));
+        fs::write(project_dir.join("Makefile"), makewiz::generate_java_makefile(&java_data)).unwrap();
 
+        let status = Command::new("make").arg("-s").arg("jar").current_dir(&project_dir).status().unwrap();
+        assert!(status.success());
+
+        let list_jar = |name: &str| {
+            let output = Command::new("jar").arg("tf").arg(format!("out-jars/{}.jar", name)).current_dir(&project_dir).output().unwrap();
+            String::from_utf8(output.stdout).unwrap()
+        };
+        assert!(list_jar("com.example.app").contains("com/example/app/App.class"));
+        assert!(list_jar("com.example.greeter").contains("com/example/greeter/Greeter.class"));
+
+        fs::remove_dir_all(&project_dir).unwrap();
+    }
+
+    #[test]
+    fn java_doc_makefile_creation() {
+        let mut java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-java-package-layout")).unwrap();
+        java_data.main_class = Some(String::from("com.example.App"));
+        java_data.doc = true;
+        java_data.sources_jar = true;
+
+        let makefile = makewiz::generate_java_makefile(&java_data);
+
+        assert!(makefile.contains("\
+# Documentation and sources jar
+JAVADOC = javadoc
+DOC_DIR = out-doc
+DOC_PACKAGES = com.example com.example.util
+SOURCES_JAR = App-sources.jar
+SOURCES_JAR_DIR = src
+SOURCES_JAR_FILES = com/example/App.java com/example/util/Helper.java
+"));
+        assert!(makefile.contains("\
+# Generate the documentation with javadoc
+doc: $(DOC_DIR)/index.html
+
+$(DOC_DIR)/index.html: $(SOURCE)
+\t$(JAVADOC) -d $(DOC_DIR) -cp $(CLASS_PATH) -sourcepath $(SRC_DIR) $(DOC_PACKAGES)
+
+# Jar with the source files, laid out by package
+sources-jar: $(SOURCES_JAR)
+
+$(SOURCES_JAR): $(SOURCE)
+\t$(JAR) cf $(SOURCES_JAR) $(foreach file,$(SOURCES_JAR_FILES),-C $(SOURCES_JAR_DIR) $(file))
+"));
+        assert!(makefile.ends_with("rm -rf $(OUT_DIR) $(CLASSES_STAMP) $(SOURCES_LIST) $(MANIFEST) $(JAR_FILE) $(TEST_OUT_DIR) $(TEST_CLASSES_STAMP) $(TEST_SOURCES_LIST) $(DOC_DIR) $(SOURCES_JAR)\n"));
+    }
+
+    #[test]
+    fn jni_makefile_creation() {
+        let mut java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-jni")).unwrap();
+        java_data.main_class = Some(String::from("com.example.NativeHello"));
+
+        let mut native_data = build_data::BuildData::extract_names(fs::read_dir("./test-dirs/test-jni").unwrap()).unwrap();
+        native_data.compiler = user_config::UserConfig::default().compiler_name;
+
+        assert_eq!(vec![String::from("hello")], java_data.get_loaded_libraries());
+
+        let makefile = makewiz::generate_jni_makefile(&java_data, &native_data, "hello");
+
+        assert!(makefile.contains("\
+# Native library built from the C/C++ sources
+CC = gcc
+FLAGS = -g -c -Wall -fPIC
+LFLAGS = 
+NATIVE_OBJS = hello.o
+NATIVE_SOURCE = hello.c
+NATIVE_HEADER = greeting.h
+NATIVE_LIB = libhello.so
+LDLIBS = 
+"));
+        assert!(makefile.contains("\
 # Default target
-default: $(CLASSES)
+default: $(CLASSES_STAMP) $(NATIVE_LIB)
+"));
+        assert!(makefile.contains("\t$(JC) $(JFLAGS) -d $(OUT_DIR) -h $(JNI_HEADER_DIR) -sourcepath $(SRC_DIR) @$(SOURCES_LIST)\n"));
+        assert!(makefile.contains("\
+# Linking rule for the native library
+$(NATIVE_LIB): $(NATIVE_OBJS)
+\t$(CC) -shared $(NATIVE_OBJS) -o $(NATIVE_LIB) $(LFLAGS) $(LDLIBS)
+
+# The native sources include the headers generated by javac
+$(NATIVE_OBJS): $(CLASSES_STAMP)
+
+# Compilation rules for the native sources
+%.o: %.c $(NATIVE_HEADER)
+\t$(CC) $(FLAGS) $(JNI_INCLUDE) -o $@ $<
+"));
+        assert!(makefile.contains("\
+run: $(CLASSES_STAMP) $(NATIVE_LIB)
+\t$(JAVA) -Djava.library.path=. -cp $(OUT_DIR) $(MAIN_CLASS)
+"));
+        assert!(makefile.ends_with("rm -rf $(OUT_DIR) $(CLASSES_STAMP) $(SOURCES_LIST) $(MANIFEST) $(JAR_FILE) $(NATIVE_OBJS) $(NATIVE_LIB) $(JNI_HEADER_DIR)\n"));
+    }
+
+    #[test]
+    fn mixed_makefile_creation() {
+        let mut file_names = build_data::BuildData::extract_names(fs::read_dir("./test-dirs/test-mixed-languages").unwrap()).unwrap();
+        file_names.compiler = String::from("g++");
+        file_names.executable = String::from("tool");
+
+        let java_data = java_data::JavaData::extract_sources(Path::new("./test-dirs/test-mixed-languages")).unwrap();
+
+        let expected = "\
+# ---------- C/C++ ----------
+
+# Compiler and flags
+CC = g++
+FLAGS = -g -c -Wall
+LFLAGS = 
+
+# Source files and object files
+OBJS = tool.o
+CC_SOURCE = tool.cpp
+HEADER = tool.hpp
+OUT = tool
+
+# Libraries
+LDLIBS = 
+
+# ---------- Java ----------
+
+# Compiler and flags
+JC = javac
+JFLAGS = -g
+JAR = jar
+JAVA = java
+
+# Directories
+SRC_DIR = .
+OUT_DIR = out
+
+# Source files and compiled classes
+SOURCE = helper/Helper.java
+CLASSES_STAMP = classes.stamp
+SOURCES_LIST = sources.txt
+
+# Default target, building every language
+all: $(OUT) $(CLASSES_STAMP)
+
+# Linking rules
+$(OUT): $(OBJS)
+\t$(CC) -g $(OBJS) -o $(OUT) $(LFLAGS) $(LDLIBS)
+
+# Compilation rules
+%.o: %.cpp $(HEADER)
+\t$(CC) $(FLAGS) -o $@ $<
+
+# List of source files passed to javac
+$(SOURCES_LIST): $(SOURCE)
+\t@printf '%s\\n' $(SOURCE) > $(SOURCES_LIST)
+
+# Compilation rule, a single javac invocation compiles all classes
+$(CLASSES_STAMP): $(SOURCES_LIST)
+\t@mkdir -p $(OUT_DIR)
+\t$(JC) $(JFLAGS) -d $(OUT_DIR) -sourcepath $(SRC_DIR) @$(SOURCES_LIST)
+\t@touch $(CLASSES_STAMP)
+
+# Clean rule for every language
+clean:
+\trm -rf $(OBJS) $(OUT) $(OUT_DIR) $(CLASSES_STAMP) $(SOURCES_LIST)\n";
+        assert_eq!(expected, makewiz::generate_mixed_makefile(&file_names, &java_data));
+    }
+
+    #[test]
+    fn kotlin_makefile_creation() {
+        let root = Path::new("./test-dirs/test-kotlin");
+        let mut java_data = java_data::JavaData::extract_sources(root).unwrap();
+        java_data.extract_kotlin_sources(root).unwrap();
+        java_data.main_class = Some(String::from("com.example.AppKt"));
+
+        let expected = "\
+# Compilers and flags
+KC = kotlinc
+KFLAGS =
+JC = javac
+JFLAGS = -g
+JAR = jar
+JAVA = java
+
+# Directories
+OUT_DIR = out
+
+# Source files, Kotlin is compiled before Java
+KOTLIN_SOURCE = src/com/example/App.kt src/com/example/util/Strings.kt
+KOTLIN_STAMP = kotlin.stamp
+SOURCE = src/com/example/Greeter.java
+CLASSES_STAMP = classes.stamp
+SOURCES_LIST = sources.txt
+
+# Kotlin standard library, next to kotlinc
+KOTLIN_HOME ?= $(shell dirname $$(dirname $$(readlink -f $$(which kotlinc))))
+KOTLIN_STDLIB = $(KOTLIN_HOME)/lib/kotlin-stdlib.jar
+
+# Main class and runnable jar, with the Kotlin runtime unpacked into RUNTIME_DIR
+MAIN_CLASS = com.example.AppKt
+JAR_FILE = App.jar
+RUNTIME_DIR = out-runtime
+
+# Default target
+default: $(KOTLIN_STAMP) $(CLASSES_STAMP)
+
+# Kotlin is compiled first, kotlinc reads the Java sources to resolve the Java classes
+$(KOTLIN_STAMP): $(KOTLIN_SOURCE) $(SOURCE)
+\t@mkdir -p $(OUT_DIR)
+\t$(KC) $(KFLAGS) -d $(OUT_DIR) $(KOTLIN_SOURCE) $(SOURCE)
+\t@touch $(KOTLIN_STAMP)
+
+# List of source files passed to javac
+$(SOURCES_LIST): $(SOURCE)
+\t@printf '%s\\n' $(SOURCE) > $(SOURCES_LIST)
+
+# Java is compiled against the Kotlin classes
+$(CLASSES_STAMP): $(SOURCES_LIST) $(KOTLIN_STAMP)
+\t$(JC) $(JFLAGS) -d $(OUT_DIR) -cp $(OUT_DIR):$(KOTLIN_STDLIB) @$(SOURCES_LIST)
+\t@touch $(CLASSES_STAMP)
+
+# Runnable jar with the compiled classes and the Kotlin runtime
+jar: $(JAR_FILE)
+
+$(JAR_FILE): $(KOTLIN_STAMP) $(CLASSES_STAMP)
+\t@rm -rf $(RUNTIME_DIR) && mkdir -p $(RUNTIME_DIR)
+\tcd $(RUNTIME_DIR) && $(JAR) xf $(abspath $(KOTLIN_STDLIB))
+\t@rm -rf $(RUNTIME_DIR)/META-INF/MANIFEST.MF $(RUNTIME_DIR)/META-INF/versions
+\t$(JAR) --create --file $(JAR_FILE) --main-class $(MAIN_CLASS) -C $(OUT_DIR) . -C $(RUNTIME_DIR) .
+
+# Run the main class
+run: $(JAR_FILE)
+\t$(JAVA) -cp $(JAR_FILE) $(MAIN_CLASS)
+
+# Clean rule to remove generated .class files
+clean:
+\trm -rf $(OUT_DIR) $(KOTLIN_STAMP) $(CLASSES_STAMP) $(SOURCES_LIST) $(JAR_FILE) $(RUNTIME_DIR)\n";
+        assert_eq!(expected, makewiz::generate_kotlin_makefile(&java_data));
+    }
+
+    #[test]
+    fn scala_makefile_creation() {
+        let root = Path::new("./test-dirs/test-scala");
+        let mut java_data = java_data::JavaData::extract_sources(root).unwrap();
+        java_data.extract_scala_sources(root).unwrap();
+        java_data.main_class = Some(String::from("com.example.Launcher"));
+
+        let expected = "\
+# Compiler and flags
+SC = scalac
+SCFLAGS =
+SCALA = scala
+
+# Directories
+OUT_DIR = out
+
+# Source files
+SOURCE = src/com/example/Main.scala src/com/example/Shapes.scala
+SCALA_STAMP = scala.stamp
+
+# Main object
+MAIN_CLASS = com.example.Launcher
+
+# Default target
+default: $(SCALA_STAMP)
 
 # Compilation rule
-%.class: %.java
-\t$(JC) $(JFLAGS) $<
+$(SCALA_STAMP): $(SOURCE)
+\t@mkdir -p $(OUT_DIR)
+\t$(SC) $(SCFLAGS) -d $(OUT_DIR) $(SOURCE)
+\t@touch $(SCALA_STAMP)
+
+# Run the main object
+run: $(SCALA_STAMP)
+\t$(SCALA) -classpath $(OUT_DIR) $(MAIN_CLASS)
 
 # Clean rule to remove generated .class files
 clean:
-\trm -f $(CLASSES)\n";
-        assert_eq!(expected, makewiz::generate_java_makefile(&file_names));
+\trm -rf $(OUT_DIR) $(SCALA_STAMP)\n";
+        assert_eq!(expected, makewiz::generate_scala_makefile(&java_data));
     }
 }